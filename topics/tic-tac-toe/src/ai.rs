@@ -1,71 +1,1089 @@
-use crate::board::Board;
-use crate::game::Game;
+use crate::game::{Game, GameState};
+use crate::rng::Rng;
+use crate::strategy;
+use crate::symmetry;
 use crate::types::Player;
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+/// Opening squares the AI picks from when opening variety is enabled
+///
+/// Every first move draws under perfect play, but the center and corners
+/// leave the opponent the fewest safe replies.
+const VARIED_OPENINGS: [usize; 5] = [0, 2, 4, 6, 8];
+
+/// A set of moves leading to symmetric positions, scored once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveGroup {
+    /// The lowest position in the group, used to compute the score
+    pub representative: usize,
+    /// Score of every move in the group, from the mover's point of view
+    pub score: i32,
+    /// All positions in the group, in increasing order
+    pub moves: Vec<usize>,
+}
+
+/// Result of a position under perfect play from both sides, or of a
+/// finished game (see `GameState::outcome`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The Human side (X) wins
+    HumanWins,
+    /// Neither side can force a win
+    Draw,
+    /// The AI side (O) wins
+    AiWins,
+}
+
+/// The tunable options of an `AI`, e.g. to save and restore opponent presets
+///
+/// A custom evaluator is code rather than data, so it is not part of the
+/// configuration. With the `serde` feature it can be serialized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AiConfig {
+    /// See `AI::with_depth_weight`
+    pub depth_weight: i32,
+    /// See `AI::with_opening_variety`
+    pub opening_variety: bool,
+    /// See `AI::with_seed`
+    pub seed: u64,
+    /// See `AI::with_objective`
+    pub objective: Objective,
+    /// See `AI::with_phase_threshold` (None searches every move)
+    pub phase_threshold: Option<usize>,
+    /// See `AI::with_blunder_rate`
+    pub blunder_rate: f64,
+    /// See `AI::with_node_limit` (None searches without limit)
+    pub node_limit: Option<usize>,
+    /// See `AI::with_greediness` (None always plays a best move)
+    pub greediness: Option<f64>,
+    /// See `AI::with_search_depth` (None searches to the end of the game)
+    pub search_depth: Option<usize>,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        AI::new().config()
+    }
+}
+
+/// Playing strength presets, see `AI::with_difficulty`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    /// Plays a random move 70% of the time, the best move otherwise
+    Easy,
+    /// Looks 2 plies ahead and judges the position by `Game::balance`
+    /// there, so it blocks and wins at once but misses forks
+    Medium,
+    /// Searches every line to the end and never loses, like `AI::new`
+    #[default]
+    Hard,
+}
+
+/// What the AI plays for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Objective {
+    /// Play the first of the best-scored moves
+    #[default]
+    Optimal,
+    /// Among the best-scored moves, leave the opponent as few non-losing
+    /// replies as possible, so an imperfect opponent is likely to blunder
+    MaximizeOpponentErrors,
+    /// Play the worst-scored move, letting the opponent win whenever it can
+    /// (a teaching bot for beginners)
+    Cooperative,
+    /// Among the moves that don't lose, play the one leading to the longest
+    /// game under optimal play, even if that gives up a quicker win
+    Staller,
+}
+
+/// A suggested move and why it is good, see `AI::suggest`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// Position to play (0-8 on a 3x3 board)
+    pub position: usize,
+    /// Short phrase explaining the move, e.g. "blocks opponent's win"
+    pub reason: String,
+}
+
+/// Result of one step of a `SearchSession`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchProgress {
+    /// Number of plies searched, the root move included
+    pub depth: usize,
+    /// Best move found at this depth, None if no move is available
+    pub best_move: Option<usize>,
+    /// Score of the best move, from the mover's point of view
+    pub score: i32,
+    /// True once the search reaches the end of every line, so deeper steps
+    /// can't change the result
+    pub complete: bool,
+}
+
+/// Counters describing the AI's last search, see `AI::last_search_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Positions visited
+    pub nodes: usize,
+    /// Deepest ply reached, the root move included
+    pub max_depth: usize,
+    /// Wall-clock time of the search, when the caller timed it
+    pub elapsed: Option<Duration>,
+}
+
+impl SearchStats {
+    /// Returns a one-line summary, e.g. "visited 120 positions, 5 plies deep"
+    /// The time is included when `elapsed` is set
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "visited {} positions, {} plies deep",
+            self.nodes, self.max_depth
+        );
+        if let Some(elapsed) = self.elapsed {
+            report += &format!(", in {:.1} ms", elapsed.as_secs_f64() * 1000.0);
+        }
+        report
+    }
+}
+
+/// A move search deepened one ply at a time, e.g. to show the AI
+/// "thinking deeper" in a UI
+pub struct SearchSession<'a> {
+    ai: &'a AI,
+    game: Game,
+    /// Result of the last step, None before the first one
+    progress: Option<SearchProgress>,
+}
+
+impl SearchSession<'_> {
+    /// Searches one ply deeper than the previous step and returns the best
+    /// move at that depth
+    /// Once the search is complete, further steps return the same result
+    pub fn step(&mut self) -> SearchProgress {
+        if let Some(progress) = self.progress
+            && progress.complete
+        {
+            return progress;
+        }
+
+        let depth = self.progress.map_or(1, |progress| progress.depth + 1);
+        let (ai, game) = (self.ai, &self.game);
+        let mover = game.current_player();
+        let is_maximizing = mover.opponent() == Player::AI;
+        let best = ai.search(|| {
+            let mut best: Option<(usize, i32)> = None;
+            for position in game.available_moves() {
+                let mut next = ai.simulate_move(game, position, mover);
+                let score = ai.minimax_to(&mut next, 0, is_maximizing, depth as i32 - 1);
+                let score = if mover == Player::AI { score } else { -score };
+                if best.is_none_or(|(_, best_score)| score > best_score) {
+                    best = Some((position, score));
+                }
+            }
+            best
+        });
+
+        let progress = SearchProgress {
+            depth,
+            best_move: best.map(|(position, _)| position),
+            score: best.map_or(0, |(_, score)| score),
+            complete: depth >= self.game.available_moves().len(),
+        };
+        self.progress = Some(progress);
+        progress
+    }
+
+    /// Returns true once stepping further can't change the result
+    pub fn is_complete(&self) -> bool {
+        self.progress.is_some_and(|progress| progress.complete)
+    }
+}
+
+/// Static evaluation function scoring a position from the AI's point of view
+pub type Evaluator = Box<dyn Fn(&Game) -> i32>;
 
 /// AI player using the Minimax algorithm
 pub struct AI {
     player: Player,
+    /// How much each ply of depth costs in the score (prefer faster wins)
+    depth_weight: i32,
+    /// Pick a random strong opening on an empty board
+    opening_variety: bool,
+    /// Seed the random source was created with
+    seed: u64,
+    /// Random source for non-deterministic choices
+    rng: RefCell<Rng>,
+    /// Custom scoring of search leaves, instead of `Game::evaluate`
+    evaluator: Option<Evaluator>,
+    /// What the AI plays for
+    objective: Objective,
+    /// Above this many empty cells, play by rules of thumb instead of searching
+    phase_threshold: Option<usize>,
+    /// Probability of playing a random move instead of searching
+    blunder_rate: f64,
+    /// Most positions a single search may visit
+    node_limit: Option<usize>,
+    /// Counters of the current (or last) search
+    stats: Cell<SearchStats>,
+    /// True while a search runs, so nested searches share its counters
+    searching: Cell<bool>,
+    /// How strongly random move choices favor the best-scored moves
+    greediness: Option<f64>,
+    /// Most plies `find_best_move` looks ahead before judging the position
+    search_depth: Option<usize>,
 }
 
 impl AI {
     /// Creates a new AI instance
     pub fn new() -> Self {
-        AI { player: Player::AI }
+        AI {
+            player: Player::AI,
+            depth_weight: 1,
+            opening_variety: false,
+            seed: 0,
+            rng: RefCell::new(Rng::new(0)),
+            evaluator: None,
+            objective: Objective::Optimal,
+            phase_threshold: None,
+            blunder_rate: 0.0,
+            node_limit: None,
+            stats: Cell::new(SearchStats::default()),
+            searching: Cell::new(false),
+            greediness: None,
+            search_depth: None,
+        }
+    }
+
+    /// Creates an AI playing at the given strength
+    /// Easy's random moves come from the seeded random source, so
+    /// `with_seed` makes its games reproducible
+    pub fn with_difficulty(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => AI::new().with_blunder_rate(0.7),
+            Difficulty::Medium => AI::new().with_search_depth(2).with_evaluator(Game::balance),
+            Difficulty::Hard => AI::new(),
+        }
+    }
+
+    /// Replaces the static evaluation used to score search leaves
+    ///
+    /// The function scores a position from the AI's point of view (positive
+    /// when the AI is better). By default `Game::evaluate` is used: +10 for
+    /// an AI win, -10 for a Human win and 0 otherwise. The depth penalty is
+    /// applied to non-zero scores, moving them towards zero.
+    pub fn with_evaluator<F>(mut self, evaluator: F) -> Self
+    where
+        F: Fn(&Game) -> i32 + 'static,
+    {
+        self.evaluator = Some(Box::new(evaluator));
+        self
+    }
+
+    /// Seeds the random source used by randomized options
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = RefCell::new(Rng::new(seed));
+        self
+    }
+
+    /// Sets what the AI plays for (default `Objective::Optimal`)
+    /// With `Objective::MaximizeOpponentErrors` it still never plays a
+    /// worse move, but sets traps against imperfect opponents; with
+    /// `Objective::Cooperative` it plays to lose
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Plays the rule-based moves of `strategy::rule_based_move` while more
+    /// than `empty_cells` cells are empty, and searches only once the board
+    /// has filled up to that point
+    /// Trades some opening strength for speed on larger boards
+    pub fn with_phase_threshold(mut self, empty_cells: usize) -> Self {
+        self.phase_threshold = Some(empty_cells);
+        self
+    }
+
+    /// Makes the AI play a random legal move instead of its best one with
+    /// probability `rate` (clamped to 0-1), so it can be beaten
+    /// The random choices come from the seeded random source
+    pub fn with_blunder_rate(mut self, rate: f64) -> Self {
+        self.blunder_rate = if rate.is_nan() {
+            0.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Stops each search after visiting `max_nodes` positions, as a safety
+    /// valve for boards too large to search exhaustively
+    ///
+    /// Positions past the limit are scored by the static evaluation, and
+    /// `find_best_move` returns the best move among those it had time to
+    /// score, which is always legal. The limit applies to each call to
+    /// `find_best_move`, `evaluate_moves`, `is_losing_move` and
+    /// `SearchSession::step` as a whole, including the searches an
+    /// `Objective` or `with_greediness` runs within it.
+    pub fn with_node_limit(mut self, max_nodes: usize) -> Self {
+        self.node_limit = Some(max_nodes);
+        self
+    }
+
+    /// Picks each move at random with a probability weighted by its score,
+    /// for an opponent whose strength can be tuned smoothly
+    ///
+    /// Moves are drawn from a softmax over the `evaluate_moves` scores whose
+    /// temperature falls as `greediness` (clamped to 0-1) rises: 0 picks any
+    /// legal move uniformly and 1 always picks one of the best moves. The
+    /// random choices come from the seeded random source.
+    pub fn with_greediness(mut self, greediness: f64) -> Self {
+        self.greediness = if greediness.is_nan() {
+            None
+        } else {
+            Some(greediness.clamp(0.0, 1.0))
+        };
+        self
+    }
+
+    /// Limits every search of this AI, e.g. `find_best_move` whatever its
+    /// `Objective`, or `evaluate_moves`, to looking `plies` moves ahead, its
+    /// own included (at least 1); positions there are scored by the
+    /// evaluator as if the game had ended
+    ///
+    /// The default evaluator only knows finished games, so a shallow search
+    /// is best paired with one that judges games in progress, such as
+    /// `Game::balance`.
+    pub fn with_search_depth(mut self, plies: usize) -> Self {
+        self.search_depth = Some(plies.max(1));
+        self
+    }
+
+    /// Creates an AI with the given options
+    pub fn from_config(config: AiConfig) -> Self {
+        let mut ai = AI::new()
+            .with_depth_weight(config.depth_weight)
+            .with_opening_variety(config.opening_variety)
+            .with_seed(config.seed)
+            .with_objective(config.objective)
+            .with_blunder_rate(config.blunder_rate);
+        ai.phase_threshold = config.phase_threshold;
+        ai.node_limit = config.node_limit;
+        ai.greediness = config.greediness;
+        ai.search_depth = config.search_depth;
+        ai
+    }
+
+    /// Returns the options this AI was configured with
+    /// The random source is restarted from its seed by `from_config`
+    pub fn config(&self) -> AiConfig {
+        AiConfig {
+            depth_weight: self.depth_weight,
+            opening_variety: self.opening_variety,
+            seed: self.seed,
+            objective: self.objective,
+            phase_threshold: self.phase_threshold,
+            blunder_rate: self.blunder_rate,
+            node_limit: self.node_limit,
+            greediness: self.greediness,
+            search_depth: self.search_depth,
+        }
+    }
+
+    /// When enabled, the AI opens on an empty board with a random square
+    /// among the center and corners instead of always the same one
+    /// Later moves are unaffected and stay perfect
+    pub fn with_opening_variety(mut self, enabled: bool) -> Self {
+        self.opening_variety = enabled;
+        self
+    }
+
+    /// Sets how strongly the search prefers faster wins and slower losses
+    ///
+    /// Each ply of depth moves a terminal score `weight` points towards zero
+    /// (default 1). With a weight of 0 all wins score the same regardless of
+    /// their length. Very large weights can make long wins score no better
+    /// than a draw.
+    pub fn with_depth_weight(mut self, weight: i32) -> Self {
+        self.depth_weight = weight;
+        self
     }
 
     /// Finds the best move for the AI using the Minimax algorithm
     /// Returns the position (0-8) of the best move
     pub fn find_best_move(&self, game: &Game) -> Option<usize> {
+        self.search(|| self.best_move(game))
+    }
+
+    /// Body of `find_best_move`, run as a single search
+    fn best_move(&self, game: &Game) -> Option<usize> {
         let available_moves = game.available_moves();
 
         if available_moves.is_empty() {
             return None;
         }
 
+        if self.blunder_rate > 0.0 {
+            let mut rng = self.rng.borrow_mut();
+            if rng.gen_f64() < self.blunder_rate {
+                return rng.choose(&available_moves);
+            }
+        }
+
+        if self.opening_variety && game.board().size() == 3 && game.board().is_empty() {
+            return self
+                .rng
+                .borrow_mut()
+                .choose(&game.legal_moves_among(&VARIED_OPENINGS));
+        }
+
+        if let Some(threshold) = self.phase_threshold
+            && available_moves.len() > threshold
+        {
+            return strategy::rule_based_move(game);
+        }
+
+        if let Some(greediness) = self.greediness {
+            return self.softmax_move(game, greediness);
+        }
+
+        match self.objective {
+            Objective::Optimal => {}
+            Objective::MaximizeOpponentErrors => {
+                return self
+                    .all_best_moves(game)
+                    .into_iter()
+                    .min_by_key(|&position| self.non_losing_replies(game, position));
+            }
+            Objective::Cooperative => {
+                return self
+                    .evaluate_moves(game)
+                    .into_iter()
+                    .min_by_key(|&(_, score)| score)
+                    .map(|(position, _)| position);
+            }
+            Objective::Staller => return self.stalling_move(game),
+        }
+
         let mut best_score = i32::MIN;
         let mut best_move = available_moves[0];
+        let horizon = self.horizon();
 
         // Try each available move and evaluate it
         for &position in &available_moves {
             let mut game_clone = self.simulate_move(game, position, self.player);
-            let score = self.minimax(&mut game_clone, 0, false);
+            let score = self.minimax_to(&mut game_clone, 0, false, horizon);
 
             if score > best_score {
                 best_score = score;
                 best_move = position;
             }
+            if self.node_limit_reached() {
+                break;
+            }
         }
 
+        log_event!(
+            trace,
+            "search chose position {} (score {}) after {} nodes",
+            best_move,
+            best_score,
+            self.stats.get().nodes
+        );
         Some(best_move)
     }
 
+    /// Returns the counters of the last search, e.g. from `find_best_move`
+    /// or `evaluate_moves`, including the searches it ran for an
+    /// `Objective`; the time is left for the caller to fill in
+    pub fn last_search_stats(&self) -> SearchStats {
+        self.stats.get()
+    }
+
+    /// Scores every legal move for the player to move
+    /// Scores are from the mover's point of view: higher is better for them
+    pub fn evaluate_moves(&self, game: &Game) -> Vec<(usize, i32)> {
+        self.search(|| {
+            game.available_moves()
+                .into_iter()
+                .filter_map(|position| Some((position, self.mover_score(game, position)?)))
+                .collect()
+        })
+    }
+
+    /// Returns every move sharing the best score for the player to move
+    pub fn all_best_moves(&self, game: &Game) -> Vec<usize> {
+        let scores = self.evaluate_moves(game);
+        let Some(best) = scores.iter().map(|&(_, score)| score).max() else {
+            return Vec::new();
+        };
+
+        scores
+            .into_iter()
+            .filter(|&(_, score)| score == best)
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Groups the legal moves into tiers of equal score, best tier first,
+    /// each tier in position order
+    /// Scores are those of `evaluate_moves`, so wins of different lengths
+    /// land in different tiers; empty once the game is over
+    pub fn ranked_moves(&self, game: &Game) -> Vec<Vec<usize>> {
+        if game.state() != GameState::InProgress {
+            return Vec::new();
+        }
+
+        let mut scores = self.evaluate_moves(game);
+        scores.sort_by_key(|&(position, score)| (std::cmp::Reverse(score), position));
+
+        let mut tiers: Vec<Vec<usize>> = Vec::new();
+        let mut last_score = None;
+        for (position, score) in scores {
+            match tiers.last_mut() {
+                Some(tier) if last_score == Some(score) => tier.push(position),
+                _ => tiers.push(vec![position]),
+            }
+            last_score = Some(score);
+        }
+        tiers
+    }
+
+    /// Classifies the position as a win, draw or loss under perfect play
+    ///
+    /// This is an exact solve of the game (`Game::solve`), independent of
+    /// any AI's configuration; a finished game is classified by its result.
+    pub fn classify(game: &Game) -> Outcome {
+        let winner = match game.state() {
+            GameState::Won(player) => Some(player),
+            GameState::Draw => None,
+            GameState::InProgress => match game.solve().0 {
+                1 => Some(game.current_player()),
+                -1 => Some(game.current_player().opponent()),
+                _ => None,
+            },
+        };
+        match winner {
+            Some(Player::Human) => Outcome::HumanWins,
+            Some(Player::AI) => Outcome::AiWins,
+            None => Outcome::Draw,
+        }
+    }
+
+    /// Returns how many more moves the AI needs to win if it can force a
+    /// win, or None otherwise; Some(0) once the AI has won
+    ///
+    /// Like `classify`, this is an exact solve (`Game::solve`), with the AI
+    /// winning as fast as possible and the Human resisting as long as
+    /// possible, independent of any AI's configuration.
+    pub fn moves_to_win(game: &Game) -> Option<usize> {
+        match game.state() {
+            GameState::Won(Player::AI) => return Some(0),
+            GameState::InProgress => {}
+            _ => return None,
+        }
+
+        let ai_to_move = game.current_player() == Player::AI;
+        let (value, plies) = game.solve();
+        if value != if ai_to_move { 1 } else { -1 } {
+            return None;
+        }
+
+        // Count the AI's moves among the plies up to the win
+        Some(if ai_to_move {
+            plies.div_ceil(2)
+        } else {
+            plies / 2
+        })
+    }
+
+    /// Picks the move leaving the best `Game::heuristic` for the player to
+    /// move, looking one ply ahead without any search
+    /// Instant but not always optimal: it can miss an opponent's fork or
+    /// walk into one. The first such move wins ties; None once the game is
+    /// over
+    pub fn greedy_move(&self, game: &Game) -> Option<usize> {
+        if game.state() != GameState::InProgress {
+            return None;
+        }
+
+        let sign = if game.current_player() == Player::AI {
+            1
+        } else {
+            -1
+        };
+        game.available_moves()
+            .into_iter()
+            .filter_map(|position| Some((position, game.peek(position)?.heuristic())))
+            .max_by_key(|&(position, heuristic)| (sign * heuristic, std::cmp::Reverse(position)))
+            .map(|(position, _)| position)
+    }
+
+    /// Returns how good the position is for the AI as a percentage, e.g. for
+    /// a UI readout: 50 for a draw under perfect play, above 50 when the AI
+    /// wins (the sooner, the higher) and below 50 when it loses
+    ///
+    /// Like `moves_to_win`, this is an exact solve (`Game::solve`),
+    /// independent of any AI's configuration: a win `d` plies away scores
+    /// `10 - d` and a finished game scores 10, 0 or -10, mapped linearly
+    /// from -10..=10 onto 0..=100.
+    pub fn confidence(game: &Game) -> f64 {
+        let score = match game.state() {
+            GameState::InProgress => {
+                let (value, plies) = game.solve();
+                let score = value * (10 - plies as i32);
+                if game.current_player() == Player::AI {
+                    score
+                } else {
+                    -score
+                }
+            }
+            _ => game.evaluate(),
+        };
+        (50.0 + 5.0 * f64::from(score)).clamp(0.0, 100.0)
+    }
+
+    /// Measures how much the choice of move matters for the player to move:
+    /// the gap between the best and second-best scores of `evaluate_moves`
+    /// A large gap means one clearly best move (a sharp position), 0 means
+    /// the best move has an equal alternative; 0 with fewer than two moves
+    pub fn sharpness(&self, game: &Game) -> f64 {
+        let mut scores: Vec<i32> = self
+            .evaluate_moves(game)
+            .into_iter()
+            .map(|(_, score)| score)
+            .collect();
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        match scores[..] {
+            [best, second, ..] => f64::from(best - second),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the principal variation: the sequence of moves both sides
+    /// play from here if they always pick their best move
+    pub fn best_line(&self, game: &Game) -> Vec<usize> {
+        let mut line = Vec::new();
+        let mut game = game.clone();
+
+        while let Some(&position) = self.all_best_moves(&game).first() {
+            if !game.apply_move(position) {
+                break;
+            }
+            line.push(position);
+        }
+
+        line
+    }
+
+    /// Returns the best move for the player to move, paired with the
+    /// opponent's best reply to it (None if that move ends the game)
+    /// Returns None if the game is already over
+    pub fn best_pair(&self, game: &Game) -> Option<(usize, Option<usize>)> {
+        let line = self.best_line(game);
+        Some((*line.first()?, line.get(1).copied()))
+    }
+
+    /// Starts a search for the player to move that is deepened one ply per
+    /// `SearchSession::step`; at full depth it agrees with `all_best_moves`
+    pub fn search_session(&self, game: &Game) -> SearchSession<'_> {
+        SearchSession {
+            ai: self,
+            game: game.clone(),
+            progress: None,
+        }
+    }
+
+    /// Suggests the best move for the player to move, with a short reason
+    /// such as "wins immediately" or "creates a fork", e.g. for a hint
+    /// Returns None once the game is over
+    pub fn suggest(&self, game: &Game) -> Option<Suggestion> {
+        if game.state() != GameState::InProgress {
+            return None;
+        }
+
+        let (position, _) = self.first_best_move(game)?;
+        Some(Suggestion {
+            position,
+            reason: move_reason(game, position).to_string(),
+        })
+    }
+
+    /// Returns the first of the best-scored moves with its score, the move
+    /// `suggest` and `explain` describe
+    fn first_best_move(&self, game: &Game) -> Option<(usize, i32)> {
+        let scores = self.evaluate_moves(game);
+        let best = scores.iter().map(|&(_, score)| score).max()?;
+        scores.into_iter().find(|&(_, score)| score == best)
+    }
+
+    /// Explains the best move for the player to move in plain text: the
+    /// move, its score, why it matters and the principal variation
+    /// Positions are shown 1-indexed, as in the CLI
+    pub fn explain(&self, game: &Game) -> String {
+        let Some((position, score)) = self.first_best_move(game) else {
+            return String::from("No move available: the game is over");
+        };
+
+        let variation: Vec<String> = self
+            .best_line(game)
+            .iter()
+            .map(|position| (position + 1).to_string())
+            .collect();
+
+        format!(
+            "Best move: position {}, {} (score {})\nReason: {}\nPrincipal variation: {}",
+            position + 1,
+            game.board().describe_move(position),
+            score,
+            move_reason(game, position),
+            variation.join(" ")
+        )
+    }
+
+    /// Like `evaluate_moves`, but moves leading to symmetric positions are
+    /// collapsed into a single group and only its representative is searched
+    pub fn evaluate_moves_dedup(&self, game: &Game) -> Vec<MoveGroup> {
+        let mut groups: Vec<(u64, Vec<usize>)> = Vec::new();
+
+        for position in game.available_moves() {
+            let mut board = game.board().clone();
+            board.make_move(position, game.current_player());
+            let key = symmetry::canonical_key(&board);
+
+            match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                Some((_, moves)) => moves.push(position),
+                None => groups.push((key, vec![position])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter_map(|(_, moves)| {
+                let representative = moves[0];
+                let score = self.mover_score(game, representative)?;
+                Some(MoveGroup {
+                    representative,
+                    score,
+                    moves,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns true if playing `position` loses for the player to move,
+    /// assuming the opponent answers optimally from then on
+    /// Illegal moves are never reported as losing
+    pub fn is_losing_move(&self, game: &Game, position: usize) -> bool {
+        let mover = game.current_player();
+
+        match self.search(|| self.score_move(game, position)) {
+            Some(score) if mover == Player::AI => score < 0,
+            Some(score) => score > 0,
+            None => false,
+        }
+    }
+
+    /// Returns the moves that keep at least a draw for the player to move,
+    /// assuming optimal play from both sides afterwards
+    /// Empty once the game is over
+    pub fn drawing_moves(&self, game: &Game) -> Vec<usize> {
+        if game.state() != GameState::InProgress {
+            return Vec::new();
+        }
+
+        self.evaluate_moves(game)
+            .into_iter()
+            .filter(|&(_, score)| score >= 0)
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Returns true if playing `a` or `b` leads to different outcomes under
+    /// perfect play, as found by `classify`, e.g. to tell a player that
+    /// either move is fine
+    /// An illegal move has no outcome, so it differs from any legal one
+    pub fn changes_outcome(game: &Game, a: usize, b: usize) -> bool {
+        let outcome = |position| game.peek(position).map(|next| AI::classify(&next));
+        outcome(a) != outcome(b)
+    }
+
+    /// Returns a line by which the player to move wins within `n` of their
+    /// own moves whatever the defense, or None if there is none
+    ///
+    /// The line alternates the winner's moves with the opponent's longest
+    /// resistance and ends on the winning move, so a win in one is a single
+    /// move. The fastest forced win is returned, the first move found
+    /// winning ties. Like `classify`, this is an exact solve
+    /// (`Game::solve`), independent of any AI's configuration. Useful for
+    /// "win in N" puzzles.
+    pub fn forced_win_in(game: &Game, n: usize) -> Option<Vec<usize>> {
+        if game.state() != GameState::InProgress {
+            return None;
+        }
+        let (value, plies) = game.solve();
+        (value == 1 && plies.div_ceil(2) <= n).then(|| game.solved_line())
+    }
+
+    /// Returns the moves that force a win without winning on the spot
+    /// After such a move the game goes on, but every opponent reply still
+    /// leaves the mover a winning continuation (typically through a fork)
+    pub fn forcing_moves(&self, game: &Game) -> Vec<usize> {
+        game.available_moves()
+            .into_iter()
+            .filter(|&position| {
+                let mut next = game.clone();
+                next.apply_move(position);
+                next.state() == GameState::InProgress
+                    && next.available_moves().into_iter().all(|reply| {
+                        let mut after = next.clone();
+                        after.apply_move(reply);
+                        after.state() == GameState::InProgress
+                            && self
+                                .evaluate_moves(&after)
+                                .iter()
+                                .any(|&(_, score)| score > 0)
+                    })
+            })
+            .collect()
+    }
+
+    /// Finds the best move for the player to move against an opponent that
+    /// only looks `opponent_depth` plies ahead, its own move included
+    ///
+    /// The opponent is assumed to play the move its shallow search prefers,
+    /// scoring positions at its horizon with `Game::balance`, so the
+    /// result may set a trap a deeper opponent would avoid. A depth of 0 is
+    /// treated as 1. Returns None if no move is available.
+    pub fn best_move_vs_depth(&self, game: &Game, opponent_depth: usize) -> Option<usize> {
+        let mover = game.current_player();
+        let mut best: Option<(usize, i32)> = None;
+
+        for position in game.available_moves() {
+            let next = self.simulate_move(game, position, mover);
+            let score = self.exploit(&next, mover, opponent_depth, 1);
+            let score = if mover == Player::AI { score } else { -score };
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((position, score));
+            }
+        }
+
+        best.map(|(position, _)| position)
+    }
+
+    /// Minimax where `me` searches the whole tree while the opponent plays
+    /// its `predicted_reply`; scores are from the AI's point of view
+    fn exploit(&self, game: &Game, me: Player, opponent_depth: usize, depth: i32) -> i32 {
+        let available_moves = game.available_moves();
+        if game.check_winner(Player::AI)
+            || game.check_winner(Player::Human)
+            || available_moves.is_empty()
+        {
+            let score = self.evaluate(game);
+            return score - score.signum() * depth * self.depth_weight;
+        }
+
+        let player = game.current_player();
+        if player != me {
+            let reply = self
+                .predicted_reply(game, opponent_depth)
+                .unwrap_or(available_moves[0]);
+            let next = self.simulate_move(game, reply, player);
+            return self.exploit(&next, me, opponent_depth, depth + 1);
+        }
+
+        let scores = available_moves.iter().map(|&position| {
+            let next = self.simulate_move(game, position, player);
+            self.exploit(&next, me, opponent_depth, depth + 1)
+        });
+        match player {
+            Player::AI => scores.max(),
+            Player::Human => scores.min(),
+        }
+        .unwrap_or(0)
+    }
+
+    /// Returns the move a player searching `depth` plies ahead picks, the
+    /// first of its best-scored moves
+    /// The player is modeled as an AI judging the positions at its horizon
+    /// by `Game::balance`, like `Difficulty::Medium`
+    fn predicted_reply(&self, game: &Game, depth: usize) -> Option<usize> {
+        let model = AI::new().with_evaluator(Game::balance);
+        let player = game.current_player();
+        // The reply itself is the first ply
+        let horizon = depth.max(1) as i32 - 1;
+        let mut best: Option<(usize, i32)> = None;
+
+        for position in game.available_moves() {
+            let mut next = model.simulate_move(game, position, player);
+            let score = model.minimax_to(&mut next, 0, player == Player::Human, horizon);
+            let score = if player == Player::AI { score } else { -score };
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((position, score));
+            }
+        }
+
+        best.map(|(position, _)| position)
+    }
+
+    /// Finds the most stubborn defense for the player to move, meant for
+    /// positions they can no longer save
+    ///
+    /// Among the best-scored moves, which with the default depth weight are
+    /// those putting the loss off the longest, it picks the one leaving the
+    /// opponent the most replies that throw their advantage away. The first
+    /// such move wins ties. Returns None if no move is available.
+    pub fn best_defense(&self, game: &Game) -> Option<usize> {
+        let scores = self.evaluate_moves(game);
+        let best = scores.iter().map(|&(_, score)| score).max()?;
+
+        scores
+            .into_iter()
+            .filter(|&(_, score)| score == best)
+            .map(|(position, _)| position)
+            .max_by_key(|&position| {
+                (
+                    self.opponent_mistakes(game, position),
+                    std::cmp::Reverse(position),
+                )
+            })
+    }
+
+    /// Draws a move with probability proportional to `exp(beta * score)`,
+    /// where the inverse temperature `beta` grows from 0 to infinity as
+    /// `greediness` goes from 0 to 1
+    fn softmax_move(&self, game: &Game, greediness: f64) -> Option<usize> {
+        let scores = self.evaluate_moves(game);
+        let best = scores.iter().map(|&(_, score)| score).max()?;
+
+        // Relative to the best score, so the weights can't overflow
+        let weights: Vec<f64> = scores
+            .iter()
+            .map(|&(_, score)| {
+                if score == best {
+                    1.0
+                } else if greediness >= 1.0 {
+                    0.0
+                } else {
+                    let beta = greediness / (1.0 - greediness);
+                    (beta * f64::from(score - best)).exp()
+                }
+            })
+            .collect();
+
+        let mut target = self.rng.borrow_mut().gen_f64() * weights.iter().sum::<f64>();
+        for (&(position, _), weight) in scores.iter().zip(weights) {
+            if target < weight {
+                return Some(position);
+            }
+            target -= weight;
+        }
+        scores.last().map(|&(position, _)| position)
+    }
+
+    /// Returns the non-losing move leading to the longest game under optimal
+    /// play, or the longest-lasting move if every move loses
+    /// The first such move wins ties
+    fn stalling_move(&self, game: &Game) -> Option<usize> {
+        let scores = self.evaluate_moves(game);
+        let candidates: Vec<usize> = if scores.iter().any(|&(_, score)| score >= 0) {
+            scores
+                .iter()
+                .filter(|&&(_, score)| score >= 0)
+                .map(|&(position, _)| position)
+                .collect()
+        } else {
+            scores.iter().map(|&(position, _)| position).collect()
+        };
+
+        candidates.into_iter().max_by_key(|&position| {
+            let plies = game
+                .peek(position)
+                .map_or(0, |next| 1 + next.plies_to_terminal().unwrap_or(0));
+            (plies, std::cmp::Reverse(position))
+        })
+    }
+
+    /// Counts the opponent's replies to `position` that score worse for
+    /// them than their best reply
+    fn opponent_mistakes(&self, game: &Game, position: usize) -> usize {
+        let next = self.simulate_move(game, position, game.current_player());
+        let replies = self.evaluate_moves(&next);
+        let Some(best) = replies.iter().map(|&(_, score)| score).max() else {
+            return 0;
+        };
+        replies.iter().filter(|&&(_, score)| score < best).count()
+    }
+
+    /// Counts the opponent's replies to `position` that don't lose for them
+    fn non_losing_replies(&self, game: &Game, position: usize) -> usize {
+        let next = self.simulate_move(game, position, game.current_player());
+        self.evaluate_moves(&next)
+            .into_iter()
+            .filter(|&(_, score)| score >= 0)
+            .count()
+    }
+
+    /// Scores the position reached after the current player plays `position`
+    /// The score is from the AI's point of view, as returned by `minimax`
+    /// Returns None if the move is not legal
+    fn score_move(&self, game: &Game, position: usize) -> Option<i32> {
+        if !game.available_moves().contains(&position) {
+            return None;
+        }
+
+        let mover = game.current_player();
+        let mut game_clone = self.simulate_move(game, position, mover);
+        let is_maximizing = mover.opponent() == Player::AI;
+        Some(self.minimax_to(&mut game_clone, 0, is_maximizing, self.horizon()))
+    }
+
+    /// Depth at which searches below a root move stop, from `search_depth`
+    /// (the root move is the first ply)
+    fn horizon(&self) -> i32 {
+        self.search_depth.map_or(i32::MAX, |plies| plies as i32 - 1)
+    }
+
+    /// Like `score_move`, but from the point of view of the player to move
+    fn mover_score(&self, game: &Game, position: usize) -> Option<i32> {
+        let score = self.score_move(game, position)?;
+        match game.current_player() {
+            Player::AI => Some(score),
+            Player::Human => Some(-score),
+        }
+    }
+
     /// Minimax algorithm with depth tracking
     ///
     /// # Arguments
     /// * `game` - The current game state
     /// * `depth` - Current depth in the game tree
     /// * `is_maximizing` - True if maximizing player (AI), false if minimizing (Human)
+    /// * `horizon` - Depth at which positions are scored with the static
+    ///   evaluation as if they were leaves (`i32::MAX` for none)
     ///
     /// # Returns
     /// The score of the board state
-    fn minimax(&self, game: &mut Game, depth: i32, is_maximizing: bool) -> i32 {
-        // Terminal state: check if game is over
-        let score = game.evaluate();
-
-        // If AI won, return score minus depth (prefer faster wins)
-        if score == 10 {
-            return score - depth;
-        }
-
-        // If Human won, return score plus depth (prefer slower losses)
-        if score == -10 {
-            return score + depth;
-        }
+    fn minimax_to(&self, game: &mut Game, depth: i32, is_maximizing: bool, horizon: i32) -> i32 {
+        let mut stats = self.stats.get();
+        stats.max_depth = stats.max_depth.max(depth as usize + 1);
+        self.stats.set(stats);
 
-        // Check for draw
+        // Terminal state: a player has won, the board is full or the
+        // search horizon is reached
         let available_moves = game.available_moves();
-        if available_moves.is_empty() {
-            return 0;
+        if game.check_winner(Player::AI)
+            || game.check_winner(Player::Human)
+            || available_moves.is_empty()
+            || depth >= horizon
+            || self.count_node()
+        {
+            // Move the score towards zero by the weighted depth:
+            // prefer faster wins and slower losses
+            let score = self.evaluate(game);
+            return score - score.signum() * depth * self.depth_weight;
         }
 
         if is_maximizing {
@@ -74,7 +1092,7 @@ impl AI {
 
             for &position in &available_moves {
                 let mut game_clone = self.simulate_move(game, position, Player::AI);
-                let score = self.minimax(&mut game_clone, depth + 1, false);
+                let score = self.minimax_to(&mut game_clone, depth + 1, false, horizon);
                 best_score = best_score.max(score);
             }
 
@@ -85,7 +1103,7 @@ impl AI {
 
             for &position in &available_moves {
                 let mut game_clone = self.simulate_move(game, position, Player::Human);
-                let score = self.minimax(&mut game_clone, depth + 1, true);
+                let score = self.minimax_to(&mut game_clone, depth + 1, true, horizon);
                 best_score = best_score.min(score);
             }
 
@@ -93,30 +1111,52 @@ impl AI {
         }
     }
 
-    /// Simulates a move and returns a new game state
-    fn simulate_move(&self, game: &Game, position: usize, player: Player) -> Game {
-        // Create a copy of the current game using the board state
-        let mut new_board = Board::new();
+    /// Runs `search` with fresh counters, so the node limit covers it as a
+    /// whole; a search nested in a running one shares its counters
+    fn search<T>(&self, search: impl FnOnce() -> T) -> T {
+        if self.searching.replace(true) {
+            return search();
+        }
+        self.stats.take();
+        let result = search();
+        self.searching.set(false);
+        result
+    }
 
-        // Copy the current board state
-        for i in 0..9 {
-            if let Some(crate::types::Cell::Occupied(p)) = game.board().get(i) {
-                new_board.make_move(i, p);
-            }
+    /// Counts a visited position, returning true instead once the node
+    /// limit is used up, so a search never visits more than the limit
+    fn count_node(&self) -> bool {
+        if self.node_limit_reached() {
+            return true;
         }
+        let mut stats = self.stats.get();
+        stats.nodes += 1;
+        self.stats.set(stats);
+        false
+    }
 
-        // Make the new move on the copied board
-        new_board.make_move(position, player);
+    /// Returns true if the current search has used up its node limit
+    fn node_limit_reached(&self) -> bool {
+        self.node_limit
+            .is_some_and(|limit| self.stats.get().nodes >= limit)
+    }
 
-        // Create a new game with this board state
-        // We need to use Game::from_board or similar
-        // For now, let's create a helper in Game
-        self.create_game_from_board(new_board, player.opponent())
+    /// Scores a search leaf with the custom evaluator, or `Game::evaluate`
+    fn evaluate(&self, game: &Game) -> i32 {
+        match &self.evaluator {
+            Some(evaluator) => evaluator(game),
+            None => game.evaluate(),
+        }
     }
 
-    /// Creates a game state from a board
-    fn create_game_from_board(&self, board: Board, next_player: Player) -> Game {
-        Game::from_board(board, next_player)
+    /// Simulates `player` playing `position` and returns the new game state
+    /// The state is updated as in real play, so a winning move leaves the
+    /// game won
+    fn simulate_move(&self, game: &Game, position: usize, player: Player) -> Game {
+        // Copy the position with `player` to move, keeping the game's rules
+        let mut next = game.with_board(game.board().clone(), player);
+        next.apply_move(position);
+        next
     }
 }
 
@@ -126,9 +1166,73 @@ impl Default for AI {
     }
 }
 
+/// Describes what playing `position` does for the player to move, from
+/// the most to the least urgent: winning, blocking a win, forking
+fn move_reason(game: &Game, position: usize) -> &'static str {
+    let mover = game.current_player();
+    if game.threats(mover).contains(&position) {
+        "wins immediately"
+    } else if game.threats(mover.opponent()).contains(&position) {
+        "blocks opponent's win"
+    } else if game.creates_fork(position, mover) {
+        "creates a fork"
+    } else {
+        "best defensive move"
+    }
+}
+
+/// Renders the game tree from `game` down to `max_depth` plies as Graphviz
+/// DOT, e.g. to illustrate minimax
+///
+/// Nodes are labeled with the compact board, edges with the move played
+/// (1-indexed, as in the CLI). Leaves, whether finished games or positions
+/// at the depth limit, also show their `Game::balance`. The tree grows
+/// quickly, so keep `max_depth` small.
+pub fn export_tree(game: &Game, max_depth: usize) -> String {
+    let mut dot = String::from("digraph tree {\n");
+    let mut next_id = 0;
+    write_node(game, max_depth, &mut dot, &mut next_id);
+    dot.push_str("}\n");
+    dot
+}
+
+/// Writes the node for `game` and its subtree, returning the node's id
+fn write_node(game: &Game, depth_left: usize, dot: &mut String, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let board = game.board().to_compact();
+    let moves = if game.state() == GameState::InProgress && depth_left > 0 {
+        game.available_moves()
+    } else {
+        Vec::new()
+    };
+
+    if moves.is_empty() {
+        dot.push_str(&format!(
+            "    n{id} [label=\"{board}\\n{}\"];\n",
+            game.balance()
+        ));
+        return id;
+    }
+
+    dot.push_str(&format!("    n{id} [label=\"{board}\"];\n"));
+    for position in moves {
+        let mut next = game.clone();
+        next.apply_move(position);
+        let child = write_node(&next, depth_left - 1, dot, next_id);
+        dot.push_str(&format!(
+            "    n{id} -> n{child} [label=\"{}\"];\n",
+            position + 1
+        ));
+    }
+    id
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::board::{Board, BoardBuilder};
 
     #[test]
     fn test_ai_blocks_winning_move() {
@@ -172,4 +1276,1002 @@ mod tests {
         assert!(best_move.is_some());
         assert!(best_move.unwrap() < 9);
     }
+
+    /// Position where the AI wins at once with 5, or later with the fork at 2
+    fn immediate_or_delayed_win() -> Game {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(1); // Human X
+        game.make_move(4); // AI O
+        game.make_move(8); // Human X
+        game
+    }
+
+    #[test]
+    fn test_depth_weight_prefers_immediate_win() {
+        let game = immediate_or_delayed_win();
+
+        for weight in [1, 3] {
+            let ai = AI::new().with_depth_weight(weight);
+            assert_eq!(ai.find_best_move(&game), Some(5));
+        }
+    }
+
+    #[test]
+    fn test_zero_depth_weight_ignores_win_speed() {
+        let game = immediate_or_delayed_win();
+        let ai = AI::new().with_depth_weight(0);
+
+        // Both the immediate win and the delayed fork are equally good
+        let best_move = ai.find_best_move(&game);
+        assert!(matches!(best_move, Some(2) | Some(5)));
+        assert!(!ai.is_losing_move(&game, best_move.unwrap()));
+    }
+
+    #[test]
+    fn test_objective_prefers_trap() {
+        let mut game = Game::new();
+        game.make_move(7); // Human X
+
+        // Both replies draw, but the corner next to X's edge leaves X fewer
+        // safe moves than the opposite edge
+        let ai = AI::new();
+        let trapper = AI::new().with_objective(Objective::MaximizeOpponentErrors);
+        assert_eq!(ai.find_best_move(&game), Some(1));
+        assert_eq!(trapper.find_best_move(&game), Some(6));
+        assert!(ai.all_best_moves(&game).contains(&6));
+        assert!(trapper.non_losing_replies(&game, 6) < ai.non_losing_replies(&game, 1));
+    }
+
+    #[test]
+    fn test_objective_keeps_winning() {
+        let game = immediate_or_delayed_win();
+        let trapper = AI::new().with_objective(Objective::MaximizeOpponentErrors);
+        assert_eq!(trapper.find_best_move(&game), Some(5));
+    }
+
+    #[test]
+    fn test_cooperative_declines_to_block() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X, threatens 2
+
+        assert_eq!(AI::new().find_best_move(&game), Some(2));
+        let teacher = AI::new().with_objective(Objective::Cooperative);
+        let position = teacher.find_best_move(&game).unwrap();
+        assert_ne!(position, 2);
+        assert!(game.available_moves().contains(&position));
+
+        // Nothing to play on a full board
+        let mut game = Game::new();
+        for position in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            game.make_move(position);
+        }
+        assert_eq!(game.state(), GameState::Draw);
+        assert_eq!(teacher.find_best_move(&game), None);
+    }
+
+    #[test]
+    fn test_phase_threshold() {
+        let phased = AI::new().with_phase_threshold(4);
+
+        // Opening: 9 empty cells, above the threshold, so rules of thumb
+        let game = Game::from_board(Board::new(), Player::AI);
+        assert_eq!(phased.find_best_move(&game), Some(4));
+        assert_ne!(AI::new().find_best_move(&game), Some(4));
+
+        // Endgame: 4 empty cells, searched like the default AI
+        let mut game = Game::new();
+        for position in [0, 1, 2, 4, 7] {
+            game.make_move(position);
+        }
+        assert_eq!(game.available_moves().len(), 4);
+        assert_eq!(strategy::rule_based_move(&game), Some(6));
+        assert_eq!(phased.find_best_move(&game), Some(3));
+        assert_eq!(
+            phased.find_best_move(&game),
+            AI::new().find_best_move(&game)
+        );
+    }
+
+    #[test]
+    fn test_blunder_rate() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X - threatens 2
+
+        // Always blundering: random legal moves, not always the block
+        let careless = AI::new().with_seed(3).with_blunder_rate(1.0);
+        let moves: Vec<usize> = (0..20)
+            .map(|_| careless.find_best_move(&game).unwrap())
+            .collect();
+        assert!(
+            moves
+                .iter()
+                .all(|position| game.available_moves().contains(position))
+        );
+        assert!(moves.iter().any(|&position| position != 2));
+
+        // Never blundering: always the block
+        let careful = AI::new().with_seed(3).with_blunder_rate(0.0);
+        assert!((0..20).all(|_| careful.find_best_move(&game) == Some(2)));
+
+        assert_eq!(AI::new().with_blunder_rate(7.0).config().blunder_rate, 1.0);
+        assert_eq!(
+            AI::new().with_blunder_rate(f64::NAN).config().blunder_rate,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_greediness_one_plays_best_moves() {
+        let mut game = Game::new();
+        for position in [0, 4, 8] {
+            game.make_move(position);
+        }
+        let best = AI::new().all_best_moves(&game);
+        assert_eq!(best, vec![1, 3, 5, 7]);
+
+        let ai = AI::new().with_seed(5).with_greediness(1.0);
+        let moves: Vec<usize> = (0..50).map(|_| ai.find_best_move(&game).unwrap()).collect();
+        assert!(moves.iter().all(|position| best.contains(position)));
+        // Ties between the best moves are broken at random
+        assert!(moves.iter().any(|&position| position != moves[0]));
+    }
+
+    #[test]
+    fn test_greediness_zero_is_uniform() {
+        let mut game = Game::new();
+        for position in [0, 4, 8] {
+            game.make_move(position);
+        }
+
+        let ai = AI::new().with_seed(11).with_greediness(0.0);
+        let mut counts = [0; 9];
+        for _ in 0..600 {
+            counts[ai.find_best_move(&game).unwrap()] += 1;
+        }
+        // Each of the 6 legal moves is picked about 100 times
+        for position in game.available_moves() {
+            assert!((60..140).contains(&counts[position]), "{counts:?}");
+        }
+        assert_eq!(counts[0] + counts[4] + counts[8], 0);
+
+        // Same seed, same choices
+        let picks =
+            |ai: AI| -> Vec<usize> { (0..10).map(|_| ai.find_best_move(&game).unwrap()).collect() };
+        assert_eq!(
+            picks(AI::new().with_seed(11).with_greediness(0.0)),
+            picks(AI::new().with_seed(11).with_greediness(0.0))
+        );
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        assert_eq!(AI::new().config(), AiConfig::default());
+
+        let config = AiConfig {
+            depth_weight: 3,
+            opening_variety: true,
+            seed: 42,
+            objective: Objective::MaximizeOpponentErrors,
+            phase_threshold: Some(4),
+            blunder_rate: 0.25,
+            node_limit: Some(10_000),
+            greediness: Some(0.5),
+            search_depth: Some(3),
+        };
+        assert_eq!(AI::from_config(config).config(), config);
+
+        let ai = AI::new().with_seed(7).with_depth_weight(0);
+        assert_eq!(AI::from_config(ai.config()).config(), ai.config());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_is_serializable() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+        assert_serde::<AiConfig>();
+        assert_serde::<Objective>();
+    }
+
+    #[test]
+    fn test_opening_variety() {
+        let game = Game::from_board(Board::new(), Player::AI);
+
+        let openings: Vec<usize> = (0..20)
+            .map(|seed| {
+                let ai = AI::new().with_opening_variety(true).with_seed(seed);
+                ai.find_best_move(&game).unwrap()
+            })
+            .collect();
+
+        assert!(openings.iter().all(|mv| VARIED_OPENINGS.contains(mv)));
+        assert!(openings.iter().any(|&mv| mv != openings[0]));
+    }
+
+    #[test]
+    fn test_opening_variety_only_affects_first_move() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(1); // Human X
+
+        // Still blocks the top row, whatever the seed
+        for seed in 0..5 {
+            let ai = AI::new().with_opening_variety(true).with_seed(seed);
+            assert_eq!(ai.find_best_move(&game), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_evaluate_moves() {
+        let game = immediate_or_delayed_win();
+        let ai = AI::new();
+
+        let scores = ai.evaluate_moves(&game);
+        let moves: Vec<usize> = scores.iter().map(|&(position, _)| position).collect();
+        assert_eq!(moves, game.available_moves());
+
+        // The immediate win scores best, the fork next, the rest lose
+        let score_of = |position| scores.iter().find(|s| s.0 == position).unwrap().1;
+        assert!(score_of(5) > score_of(2));
+        assert!(score_of(2) > 0);
+        assert!(score_of(6) < 0);
+    }
+
+    #[test]
+    fn test_all_best_moves_reply_to_center() {
+        let mut game = Game::new();
+        game.make_move(4); // Human X takes the center
+        let ai = AI::new();
+
+        // Only a corner reply holds the draw; every edge loses
+        assert_eq!(ai.all_best_moves(&game), vec![0, 2, 6, 8]);
+    }
+
+    #[test]
+    fn test_all_best_moves_empty_board() {
+        let game = Game::new();
+        let ai = AI::new();
+
+        // Every opening draws under perfect play, so all squares tie
+        assert_eq!(ai.all_best_moves(&game), (0..9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_all_best_moves_single_win() {
+        let game = immediate_or_delayed_win();
+        let ai = AI::new();
+        assert_eq!(ai.all_best_moves(&game), vec![5]);
+    }
+
+    #[test]
+    fn test_best_line() {
+        let game = immediate_or_delayed_win();
+        let ai = AI::new();
+        assert_eq!(ai.best_line(&game), vec![5]);
+
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+        let line = ai.best_line(&game);
+        assert_eq!(line[0], 2);
+        // Perfect play from here ends in a full-board draw
+        assert_eq!(line.len(), 6);
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(AI::classify(&Game::new()), Outcome::Draw);
+
+        // O threatens 2, 3 and 8 with X to move
+        let mut game = Game::new();
+        for position in [1, 0, 5, 4, 7, 6] {
+            game.make_move(position);
+        }
+        assert_eq!(game.current_player(), Player::Human);
+        assert_eq!(AI::classify(&game), Outcome::AiWins);
+
+        assert_eq!(AI::classify(&immediate_or_delayed_win()), Outcome::AiWins);
+        let mut game = Game::new();
+        for position in [0, 4, 1, 3] {
+            game.make_move(position);
+        }
+        assert_eq!(AI::classify(&game), Outcome::HumanWins);
+
+        // A finished game is classified by its result
+        game.make_move(2);
+        assert_eq!(AI::classify(&game), Outcome::HumanWins);
+        let drawn = Game::from_moves_str("1 5 9 2 8 7 3 6 4").unwrap();
+        assert_eq!(AI::classify(&drawn), Outcome::Draw);
+    }
+
+    #[test]
+    fn test_moves_to_win() {
+        assert_eq!(AI::moves_to_win(&Game::new()), None);
+
+        // O wins at once on 5
+        let mut game = immediate_or_delayed_win();
+        assert_eq!(AI::moves_to_win(&game), Some(1));
+        game.make_move(5);
+        assert_eq!(AI::moves_to_win(&game), Some(0));
+
+        // O threatens 2, 3 and 8 with X to move: O wins on its next move
+        let mut game = Game::new();
+        for position in [1, 0, 5, 4, 7, 6] {
+            game.make_move(position);
+        }
+        assert_eq!(AI::moves_to_win(&game), Some(1));
+
+        // Losing for the AI
+        let mut game = Game::new();
+        for position in [0, 4, 1, 3] {
+            game.make_move(position);
+        }
+        assert_eq!(AI::moves_to_win(&game), None);
+    }
+
+    #[test]
+    fn test_moves_to_win_through_fork() {
+        let mut game = Game::new();
+        game.make_move(1); // Human X
+        game.make_move(4); // AI O
+        game.make_move(7); // Human X
+        game.make_move(0); // AI O - threatens 8
+        game.make_move(8); // Human X blocks, threatening 6
+
+        // O blocks at 6, which forks 2 and 3, then wins on its next move
+        assert_eq!(AI::moves_to_win(&game), Some(2));
+    }
+
+    #[test]
+    fn test_best_pair() {
+        let ai = AI::new();
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+
+        let line = ai.best_line(&game);
+        assert_eq!(ai.best_pair(&game), Some((line[0], Some(line[1]))));
+
+        // The winning move has no reply
+        let game = immediate_or_delayed_win();
+        assert_eq!(ai.best_pair(&game), Some((5, None)));
+    }
+
+    #[test]
+    fn test_best_pair_game_over() {
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+        assert_eq!(AI::new().best_pair(&game), None);
+    }
+
+    #[test]
+    fn test_explain_immediate_win() {
+        let game = immediate_or_delayed_win();
+        let explanation = AI::new().explain(&game);
+
+        assert!(explanation.contains("Best move: position 6, middle-right edge"));
+        assert!(explanation.contains("Reason: wins immediately"));
+        assert!(explanation.contains("Principal variation: 6"));
+    }
+
+    #[test]
+    fn test_explain_block() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+
+        let explanation = AI::new().explain(&game);
+        assert!(explanation.contains("Best move: position 3"));
+        assert!(explanation.contains("Reason: blocks opponent's win"));
+    }
+
+    #[test]
+    fn test_evaluate_moves_dedup_empty_board() {
+        let game = Game::new();
+        let ai = AI::new();
+
+        let groups = ai.evaluate_moves_dedup(&game);
+        assert_eq!(groups.len(), 3);
+
+        // Corners, edges and center, each represented by its lowest square
+        assert_eq!(groups[0].representative, 0);
+        assert_eq!(groups[0].moves, vec![0, 2, 6, 8]);
+        assert_eq!(groups[1].representative, 1);
+        assert_eq!(groups[1].moves, vec![1, 3, 5, 7]);
+        assert_eq!(groups[2].representative, 4);
+        assert_eq!(groups[2].moves, vec![4]);
+
+        // Every opening draws with perfect play
+        assert!(groups.iter().all(|group| group.score == 0));
+    }
+
+    #[test]
+    fn test_flat_evaluator_picks_first_move() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(3); // Human X, threatens 6
+        assert_eq!(AI::new().find_best_move(&game), Some(6));
+
+        // Every leaf scores the same, so the first available move wins out,
+        // even though it fails to block the Human at 6
+        let ai = AI::new().with_evaluator(|_| 0);
+        assert_eq!(ai.find_best_move(&game), Some(1));
+
+        let game = immediate_or_delayed_win();
+        assert_eq!(ai.find_best_move(&game), Some(2));
+    }
+
+    #[test]
+    fn test_custom_evaluator_replaces_default() {
+        let game = immediate_or_delayed_win();
+
+        // Rewards the Human's wins instead: the AI now avoids winning at 5
+        let ai = AI::new().with_evaluator(|game: &Game| -game.evaluate());
+        assert_ne!(ai.find_best_move(&game), Some(5));
+
+        // Reproducing the default evaluation keeps the default behavior
+        let ai = AI::new().with_evaluator(Game::evaluate);
+        assert_eq!(ai.find_best_move(&game), Some(5));
+    }
+
+    #[test]
+    fn test_forcing_moves_double_corner_fork() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(8); // Human X
+        game.make_move(2); // AI O - threatens 6
+
+        // Blocking at 6 gives X three corners: threats at 3 and 7
+        let ai = AI::new();
+        assert_eq!(ai.forcing_moves(&game), vec![6]);
+    }
+
+    #[test]
+    fn test_forcing_moves_excludes_immediate_wins() {
+        let game = immediate_or_delayed_win();
+
+        // 5 wins on the spot; 2 blocks and forks (threats at 5 and 6)
+        let ai = AI::new();
+        assert_eq!(ai.forcing_moves(&game), vec![2]);
+        assert!(ai.forcing_moves(&Game::new()).is_empty());
+    }
+
+    #[test]
+    fn test_is_losing_move_single_defense() {
+        let mut game = Game::new();
+        let ai = AI::new();
+
+        // Human threatens the top row, AI must block at 2
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+
+        assert!(!ai.is_losing_move(&game, 2));
+        for position in [3, 5, 6, 7, 8] {
+            assert!(ai.is_losing_move(&game, position));
+        }
+    }
+
+    #[test]
+    fn test_is_losing_move_for_human() {
+        let mut game = Game::new();
+        let ai = AI::new();
+
+        // AI threatens the middle row, Human must block at 5
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(8); // Human X
+        game.make_move(4); // AI O
+
+        assert!(!ai.is_losing_move(&game, 5));
+        assert!(ai.is_losing_move(&game, 1));
+        // Occupied cells are not moves at all
+        assert!(!ai.is_losing_move(&game, 0));
+    }
+
+    #[test]
+    fn test_drawing_moves() {
+        let mut game = Game::new();
+        let ai = AI::new();
+
+        // Against a center opening only the corners hold the draw
+        game.make_move(4);
+        assert_eq!(ai.drawing_moves(&game), vec![0, 2, 6, 8]);
+        assert!(ai.is_losing_move(&game, 1));
+
+        // With the top row threatened, blocking is the only safe move
+        game.make_move(0);
+        game.make_move(1);
+        game.make_move(7);
+        game.make_move(2);
+        assert_eq!(ai.drawing_moves(&game), vec![6]);
+    }
+
+    #[test]
+    fn test_changes_outcome() {
+        // The center and a corner both keep the draw
+        let game = Game::new();
+        assert!(!AI::changes_outcome(&game, 4, 0));
+
+        // X can complete the top row or let O complete the middle one
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        assert!(AI::changes_outcome(&game, 2, 8));
+        assert!(!AI::changes_outcome(&game, 6, 8));
+        // An occupied cell is not a move at all
+        assert!(AI::changes_outcome(&game, 2, 0));
+    }
+
+    #[test]
+    fn test_greedy_move() {
+        let ai = AI::new();
+
+        // The center lies on the most lines
+        let mut game = Game::new();
+        assert_eq!(ai.greedy_move(&game), Some(4));
+
+        // Completing a line beats blocking the top row
+        for position in [0, 4, 1, 3, 8] {
+            game.make_move(position);
+        }
+        assert_eq!(ai.greedy_move(&game), Some(5));
+
+        game.make_move(5);
+        assert_eq!(ai.greedy_move(&game), None);
+    }
+
+    #[test]
+    fn test_forced_win_in_one() {
+        let game = Game::from_moves_str("1 4 2 5").unwrap();
+        assert_eq!(AI::forced_win_in(&game, 1), Some(vec![2]));
+        assert_eq!(AI::forced_win_in(&game, 3), Some(vec![2]));
+        assert_eq!(AI::forced_win_in(&game, 0), None);
+    }
+
+    #[test]
+    fn test_forced_win_in_two() {
+        // X wins by forking, on 5 or 9
+        let game = Game::from_moves_str("1 2 7 4").unwrap();
+        assert_eq!(AI::forced_win_in(&game, 1), None);
+
+        let line = AI::forced_win_in(&game, 2).unwrap();
+        assert_eq!(line.len(), 3);
+        assert!(game.creates_fork(line[0], Player::Human));
+        let mut replay = game.clone();
+        for &position in &line {
+            assert!(replay.make_move(position));
+        }
+        assert_eq!(replay.state(), GameState::Won(Player::Human));
+    }
+
+    #[test]
+    fn test_no_forced_win() {
+        // O answered the center with a corner: a draw
+        let game = Game::from_moves_str("5 1").unwrap();
+        assert_eq!(AI::forced_win_in(&game, 4), None);
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert_eq!(AI::confidence(&Game::new()), 50.0);
+
+        // O to move can complete the middle row
+        let winning = Game::from_moves_str("1 4 2 5 9").unwrap();
+        assert_eq!(AI::confidence(&winning), 95.0);
+
+        // X to move can complete the top row
+        let losing = Game::from_moves_str("1 4 2 5").unwrap();
+        assert_eq!(AI::confidence(&losing), 5.0);
+
+        // O forks after X blocks: a slower win rates lower
+        let fork = Game::from_moves_str("2 1 3 7").unwrap();
+        let confidence = AI::confidence(&fork);
+        assert!(confidence > 50.0 && confidence < 95.0, "{confidence}");
+
+        // A finished game is certain
+        let won = Game::from_moves_str("1 4 2 5 9 6").unwrap();
+        assert_eq!(AI::confidence(&won), 100.0);
+    }
+
+    #[test]
+    fn test_ranked_moves() {
+        // X forks on 9; taking 4 lets O fork instead
+        let game = Game::from_moves_str("1 2 3 7").unwrap();
+        let ai = AI::new();
+        assert_eq!(
+            ai.ranked_moves(&game),
+            vec![vec![8], vec![4, 5, 7], vec![3]]
+        );
+        assert!(
+            ai.evaluate_moves(&game)
+                .iter()
+                .all(|&(position, score)| match position {
+                    8 => score > 0,
+                    3 => score < 0,
+                    _ => score == 0,
+                })
+        );
+
+        let game = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert!(ai.ranked_moves(&game).is_empty());
+    }
+
+    #[test]
+    fn test_drawing_moves_finished_game() {
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+        assert!(AI::new().drawing_moves(&game).is_empty());
+    }
+
+    #[test]
+    fn test_best_move_vs_depth_sets_trap() {
+        // Human X on 0 and 7, AI O on 8: a draw under perfect play
+        let mut game = Game::new();
+        for position in [0, 8, 7] {
+            game.make_move(position);
+        }
+        let ai = AI::new();
+        assert_eq!(AI::classify(&game), Outcome::Draw);
+
+        // A shallow opponent won't see the threat on 5 coming
+        assert_eq!(ai.best_move_vs_depth(&game, 1), Some(2));
+        let mut trap = game.clone();
+        trap.make_move(2);
+        assert_ne!(ai.predicted_reply(&trap, 1), Some(5));
+        assert_eq!(ai.predicted_reply(&trap, 2), Some(5));
+
+        // Against a full-depth opponent it plays a perfect move
+        let deep = ai.best_move_vs_depth(&game, 9).unwrap();
+        assert!(ai.all_best_moves(&game).contains(&deep));
+
+        // Nothing to play on a full board
+        let mut game = Game::new();
+        for position in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            game.make_move(position);
+        }
+        assert_eq!(ai.best_move_vs_depth(&game, 1), None);
+    }
+
+    #[test]
+    fn test_search_session_matches_find_best_move() {
+        // Human X on 0 and 4, AI O to move must block on 8
+        let mut game = Game::new();
+        for position in [0, 1, 4] {
+            game.make_move(position);
+        }
+        let ai = AI::new();
+        let mut session = ai.search_session(&game);
+        assert!(!session.is_complete());
+
+        let mut depths = Vec::new();
+        let last = loop {
+            let progress = session.step();
+            depths.push(progress.depth);
+            if progress.complete {
+                break progress;
+            }
+        };
+
+        assert_eq!(depths, (1..=6).collect::<Vec<_>>());
+        assert!(session.is_complete());
+        assert_eq!(last.best_move, ai.find_best_move(&game));
+        assert_eq!(last.best_move, Some(8));
+        // Stepping a complete search changes nothing
+        assert_eq!(session.step(), last);
+    }
+
+    #[test]
+    fn test_search_session_full_board() {
+        let mut game = Game::new();
+        for position in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            game.make_move(position);
+        }
+        let progress = AI::new().search_session(&game).step();
+        assert_eq!(progress.best_move, None);
+        assert!(progress.complete);
+    }
+
+    #[test]
+    fn test_export_tree_depth_one() {
+        let dot = export_tree(&Game::new(), 1);
+
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    n0 [label=\".........\"];\n"));
+        // One child per first move, each a leaf with its balance
+        assert_eq!(dot.matches("n0 -> ").count(), 9);
+        assert!(dot.contains("    n1 [label=\"X........\\n-3\"];\n"));
+        assert!(dot.contains("    n0 -> n5 [label=\"5\"];\n"));
+        assert_eq!(dot.matches("[label=\"").count(), 19);
+    }
+
+    #[test]
+    fn test_export_tree_stops_at_game_end() {
+        // X wins on 2, so that branch has no children
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        let dot = export_tree(&game, 2);
+        assert!(dot.contains("    n1 [label=\"XXXOO....\\n-1000\"];\n"));
+        assert!(!dot.contains("n1 -> "));
+        assert_eq!(export_tree(&game, 0).matches("->").count(), 0);
+    }
+
+    #[test]
+    fn test_suggest_reasons() {
+        let ai = AI::new();
+        let suggest = |moves: &str| {
+            let suggestion = ai.suggest(&Game::from_moves_str(moves).unwrap()).unwrap();
+            (suggestion.position, suggestion.reason)
+        };
+
+        assert_eq!(suggest("1 4 2 5"), (2, "wins immediately".to_string()));
+        assert_eq!(suggest("1 5 2"), (2, "blocks opponent's win".to_string()));
+        // X at 3 threatens both 5 (middle row) and 6 (left column)
+        assert_eq!(suggest("1 2 5 9"), (3, "creates a fork".to_string()));
+        assert_eq!(suggest(""), (0, "best defensive move".to_string()));
+
+        let won = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert_eq!(ai.suggest(&won), None);
+    }
+
+    #[test]
+    fn test_difficulty_hard_is_default() {
+        assert_eq!(Difficulty::default(), Difficulty::Hard);
+        assert_eq!(
+            AI::with_difficulty(Difficulty::Hard).config(),
+            AI::new().config()
+        );
+    }
+
+    #[test]
+    fn test_search_depth_limits_every_search() {
+        let game = Game::from_moves_str("1 5 2").unwrap();
+        for objective in [
+            Objective::Optimal,
+            Objective::MaximizeOpponentErrors,
+            Objective::Cooperative,
+            Objective::Staller,
+        ] {
+            let ai = AI::new()
+                .with_search_depth(2)
+                .with_evaluator(Game::balance)
+                .with_objective(objective);
+            ai.find_best_move(&game);
+            assert!(ai.last_search_stats().max_depth <= 2, "{objective:?}");
+        }
+
+        let ai = AI::new().with_search_depth(2);
+        ai.evaluate_moves(&game);
+        assert_eq!(ai.last_search_stats().max_depth, 2);
+    }
+
+    #[test]
+    fn test_difficulty_medium_looks_two_plies_ahead() {
+        let medium = AI::with_difficulty(Difficulty::Medium);
+        let hard = AI::new();
+
+        // Immediate wins and blocks are within reach
+        let game = Game::from_moves_str("1 4 2 5").unwrap();
+        assert_eq!(medium.find_best_move(&game), Some(5));
+        let game = Game::from_moves_str("1 5 2").unwrap();
+        assert_eq!(medium.find_best_move(&game), Some(2));
+        assert_eq!(medium.last_search_stats().max_depth, 2);
+
+        // X threatens a fork that only a deeper search sees coming
+        let game = Game::from_moves_str("2 1 6").unwrap();
+        let position = medium.find_best_move(&game).unwrap();
+        assert!(hard.is_losing_move(&game, position));
+        let position = hard.find_best_move(&game).unwrap();
+        assert!(!hard.is_losing_move(&game, position));
+    }
+
+    #[test]
+    fn test_difficulty_easy_is_mostly_random() {
+        // O must block at 2; a random move rarely does
+        let game = Game::from_moves_str("1 5 2").unwrap();
+        let misses = (0..200)
+            .filter(|&seed| {
+                let easy = AI::with_difficulty(Difficulty::Easy).with_seed(seed);
+                easy.find_best_move(&game) != Some(2)
+            })
+            .count();
+        // 70% random moves, 6 of the 7 missing the block: about 60% misses
+        assert!((80..160).contains(&misses), "{misses} misses");
+
+        // The same seed replays the same choices
+        let moves = |seed| {
+            let easy = AI::with_difficulty(Difficulty::Easy).with_seed(seed);
+            (0..10)
+                .map(|_| easy.find_best_move(&game))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(moves(5), moves(5));
+    }
+
+    #[test]
+    fn test_sharpness() {
+        let ai = AI::new();
+
+        // O must block at 3: every other move loses at once
+        let must_block = Game::from_moves_str("1 5 2").unwrap();
+        assert!(ai.sharpness(&must_block) >= 5.0);
+
+        // Every opening draws
+        assert_eq!(ai.sharpness(&Game::new()), 0.0);
+
+        let one_move_left = Game::from_moves_str("1 2 3 5 4 7 8 9").unwrap();
+        assert_eq!(ai.sharpness(&one_move_left), 0.0);
+    }
+
+    #[test]
+    fn test_search_stats_report() {
+        let ai = AI::new();
+        let game = Game::from_moves_str("5 1 9").unwrap();
+        ai.find_best_move(&game);
+
+        let stats = ai.last_search_stats();
+        // 6 empty cells: every line ends within 6 plies
+        assert!(stats.nodes > 0);
+        assert!((1..=6).contains(&stats.max_depth));
+        let report = stats.report();
+        assert!(report.contains(&stats.nodes.to_string()));
+        assert!(report.contains(&format!("{} plies", stats.max_depth)));
+        assert!(!report.contains("ms"));
+
+        let timed = SearchStats {
+            nodes: 42,
+            max_depth: 3,
+            elapsed: Some(Duration::from_millis(5)),
+        };
+        assert_eq!(
+            timed.report(),
+            "visited 42 positions, 3 plies deep, in 5.0 ms"
+        );
+    }
+
+    #[test]
+    fn test_node_limit_returns_legal_move() {
+        // A full search of the empty 4x4 board would take far too long
+        let game = Game::from_board(Board::with_size(4).unwrap(), Player::AI);
+        let ai = AI::new().with_node_limit(1_000);
+        let position = ai.find_best_move(&game).unwrap();
+        assert!(game.available_moves().contains(&position));
+
+        let ai = AI::new().with_node_limit(0);
+        let position = ai.find_best_move(&Game::new()).unwrap();
+        assert!(Game::new().available_moves().contains(&position));
+    }
+
+    #[test]
+    fn test_high_node_limit_plays_perfectly() {
+        let limited = AI::new().with_node_limit(1_000_000);
+        let perfect = AI::new();
+
+        let mut game = Game::new();
+        game.make_move(0);
+        for position in [4, 8] {
+            assert_eq!(limited.find_best_move(&game), perfect.find_best_move(&game));
+            assert_eq!(limited.evaluate_moves(&game), perfect.evaluate_moves(&game));
+            game.make_move(position);
+        }
+        assert_eq!(limited.find_best_move(&game), perfect.find_best_move(&game));
+    }
+
+    #[test]
+    fn test_search_stats_cover_objective_search() {
+        // Every move draws, so each one is a candidate
+        let game = Game::from_moves_str("1 5").unwrap();
+        let ai = AI::new().with_objective(Objective::MaximizeOpponentErrors);
+        ai.find_best_move(&game);
+        let nodes = ai.last_search_stats().nodes;
+
+        // The root moves, then the replies to every best candidate
+        let plain = AI::new();
+        plain.evaluate_moves(&game);
+        let mut expected = plain.last_search_stats().nodes;
+        let candidates = plain.all_best_moves(&game);
+        assert_eq!(candidates.len(), 7);
+        for position in candidates {
+            plain.evaluate_moves(&game.peek(position).unwrap());
+            expected += plain.last_search_stats().nodes;
+        }
+        assert_eq!(nodes, expected);
+    }
+
+    #[test]
+    fn test_node_limit_covers_objective_search() {
+        // Each candidate's replies are searched within the same limit
+        let game = Game::from_moves_str("1").unwrap();
+        for limit in [0, 50, 500] {
+            let ai = AI::new()
+                .with_objective(Objective::MaximizeOpponentErrors)
+                .with_node_limit(limit);
+            let position = ai.find_best_move(&game).unwrap();
+            assert!(game.available_moves().contains(&position));
+            assert!(ai.last_search_stats().nodes <= limit);
+        }
+    }
+
+    #[test]
+    fn test_best_defense_delays_loss() {
+        // X on 0 and 3 threatens 6 and wins anyway after the block
+        let mut game = Game::new();
+        for position in [0, 1, 3] {
+            game.make_move(position);
+        }
+        let ai = AI::new();
+        assert_eq!(AI::classify(&game), Outcome::HumanWins);
+
+        let scores = ai.evaluate_moves(&game);
+        let longest = scores.iter().map(|&(_, score)| score).max();
+        let defense = ai.best_defense(&game).unwrap();
+        assert_eq!(defense, 6);
+        assert_eq!(
+            scores.iter().find(|&&(p, _)| p == defense).map(|&(_, s)| s),
+            longest
+        );
+    }
+
+    #[test]
+    fn test_best_defense_leaves_most_mistakes() {
+        // X threatens 6 and 8: every O move loses at once, but blocking
+        // gives X a chance to miss the remaining win
+        let mut game = Game::new();
+        for position in [0, 1, 2, 3, 4] {
+            game.make_move(position);
+        }
+        let ai = AI::new();
+        assert_eq!(ai.all_best_moves(&game), vec![5, 6, 7, 8]);
+        assert_eq!(ai.best_defense(&game), Some(6));
+
+        let mut game = Game::new();
+        for position in [0, 1, 2, 4, 3, 5, 7, 6, 8] {
+            game.make_move(position);
+        }
+        assert_eq!(ai.best_defense(&game), None);
+    }
+
+    #[test]
+    fn test_staller_prolongs_game() {
+        // O can win at once on 5, or block on 2 and win later
+        let board = BoardBuilder::new().x(0).x(1).x(8).o(3).o(4).build();
+        let game = Game::from_board(board, Player::AI);
+        assert_eq!(AI::new().find_best_move(&game), Some(5));
+
+        let staller = AI::new().with_objective(Objective::Staller);
+        assert_eq!(staller.find_best_move(&game), Some(2));
+        let next = game.peek(2).unwrap();
+        assert!(next.plies_to_terminal() > Some(0));
+        assert_eq!(AI::classify(&next), Outcome::AiWins);
+    }
+
+    #[test]
+    fn test_staller_avoids_losing() {
+        // X threatens 2: every other O move loses, so O must block
+        let mut game = Game::new();
+        for position in [0, 4, 1] {
+            game.make_move(position);
+        }
+        let staller = AI::new().with_objective(Objective::Staller);
+        assert_eq!(staller.find_best_move(&game), Some(2));
+    }
 }
@@ -0,0 +1,255 @@
+//! Aggregate statistics over batches of games between two strategies.
+//!
+//! Where a tournament only keeps win/draw/loss counts, a batch report also
+//! tracks how long games last, how often forks appear and how much moving
+//! first helps, for experiments comparing strategies. Tournament standings
+//! can also be turned into Elo-style ratings.
+
+use crate::game::{Game, GameState};
+use crate::rng::Rng;
+use crate::strategy::{Strategy, play_game};
+use crate::tournament::Standings;
+use crate::types::Player;
+use std::collections::HashMap;
+
+/// Rating every participant starts from
+const BASE_RATING: f64 = 1500.0;
+
+/// How far a rating moves per point of score difference on each pass
+const RATING_STEP: f64 = 100.0;
+
+/// Number of update passes, enough for the ratings to settle
+const RATING_PASSES: usize = 500;
+
+/// Aggregate results of a batch of games between strategies `a` and `b`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    /// Number of games played
+    pub games: usize,
+    /// Games won by strategy `a`
+    pub a_wins: usize,
+    /// Games won by strategy `b`
+    pub b_wins: usize,
+    /// Games drawn
+    pub draws: usize,
+    /// Games in which `a` moved first
+    pub a_moved_first: usize,
+    /// Games won by the side that moved first
+    pub first_mover_wins: usize,
+    /// Games in which either side created a fork
+    pub games_with_fork: usize,
+    /// Moves played over all games
+    pub total_moves: usize,
+}
+
+impl BatchReport {
+    /// Average number of moves per game, 0 for an empty batch
+    pub fn average_length(&self) -> f64 {
+        self.rate(self.total_moves)
+    }
+
+    /// Fraction of games that were drawn
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    /// Fraction of games in which a fork was created
+    pub fn fork_frequency(&self) -> f64 {
+        self.rate(self.games_with_fork)
+    }
+
+    /// Fraction of games won by the side that moved first
+    pub fn first_mover_win_rate(&self) -> f64 {
+        self.rate(self.first_mover_wins)
+    }
+
+    /// Returns `count` per game played, or 0 if no game was played
+    fn rate(&self, count: usize) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            count as f64 / self.games as f64
+        }
+    }
+}
+
+/// Plays `k` games between `a` and `b` and aggregates the results
+/// The side moving first is drawn at random from `seed` for every game,
+/// so the same seed always produces the same report for deterministic
+/// strategies
+pub fn run_batch(a: &dyn Strategy, b: &dyn Strategy, k: usize, seed: u64) -> BatchReport {
+    let mut rng = Rng::new(seed);
+    let mut report = BatchReport::default();
+
+    for _ in 0..k {
+        let a_first = rng.gen_range(2) == 0;
+        let (first, second) = if a_first { (a, b) } else { (b, a) };
+
+        let mut game = Game::new();
+        let state = play_game(&mut game, first, second);
+        let stats = game.stats();
+
+        report.games += 1;
+        report.total_moves += stats.total_moves;
+        if a_first {
+            report.a_moved_first += 1;
+        }
+        if stats.fork_created_by.is_some() {
+            report.games_with_fork += 1;
+        }
+
+        // The first mover plays X, the Human side
+        match state {
+            GameState::Won(winner) => {
+                let first_won = winner == Player::Human;
+                if first_won {
+                    report.first_mover_wins += 1;
+                }
+                if first_won == a_first {
+                    report.a_wins += 1;
+                } else {
+                    report.b_wins += 1;
+                }
+            }
+            GameState::Draw => report.draws += 1,
+            GameState::InProgress => {}
+        }
+    }
+
+    report
+}
+
+/// Rates every participant of a tournament on the Elo scale
+///
+/// Ratings start at 1500 and are repeatedly moved towards the score each
+/// participant actually made (a win counts 1, a draw 1/2) from the score
+/// expected against the others' ratings. Standings only keep totals, so
+/// everyone is assumed to have met everyone else equally often, as in
+/// `round_robin`. One virtual draw is added to each record so that
+/// unbeaten or winless participants still get a finite rating. The result
+/// only depends on the standings.
+pub fn compute_ratings(standings: &Standings) -> HashMap<String, f64> {
+    let records = standings.records();
+    let scores: Vec<f64> = records
+        .iter()
+        .map(|record| {
+            (record.wins as f64 + 0.5 * record.draws as f64 + 0.5) / (record.games() as f64 + 1.0)
+        })
+        .collect();
+    let mut ratings = vec![BASE_RATING; records.len()];
+
+    if records.len() > 1 {
+        for _ in 0..RATING_PASSES {
+            let expected: Vec<f64> = (0..ratings.len())
+                .map(|i| {
+                    let total: f64 = (0..ratings.len())
+                        .filter(|&j| j != i)
+                        .map(|j| 1.0 / (1.0 + 10f64.powf((ratings[j] - ratings[i]) / 400.0)))
+                        .sum();
+                    total / (ratings.len() - 1) as f64
+                })
+                .collect();
+            for (rating, (score, expected)) in ratings.iter_mut().zip(scores.iter().zip(expected)) {
+                *rating += RATING_STEP * (score - expected);
+            }
+        }
+    }
+
+    records
+        .iter()
+        .map(|record| record.name.clone())
+        .zip(ratings)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::AI;
+    use crate::strategy::RandomBot;
+
+    #[test]
+    fn test_perfect_play_always_draws() {
+        let report = run_batch(&AI::new(), &AI::new(), 2, 1);
+
+        assert_eq!(report.games, 2);
+        assert_eq!(report.draws, 2);
+        assert_eq!(report.draw_rate(), 1.0);
+        assert_eq!(report.first_mover_win_rate(), 0.0);
+        // Drawn games fill the whole board
+        assert_eq!(report.average_length(), 9.0);
+    }
+
+    #[test]
+    fn test_random_batch_totals() {
+        let report = run_batch(&RandomBot::new(1), &RandomBot::new(2), 200, 42);
+
+        assert_eq!(report.games, 200);
+        assert_eq!(report.a_wins + report.b_wins + report.draws, 200);
+        assert!(report.first_mover_wins <= report.a_wins + report.b_wins);
+        assert!(report.a_moved_first > 0 && report.a_moved_first < 200);
+        // Games last between 5 moves (fastest win) and 9 (full board)
+        assert!((5.0..=9.0).contains(&report.average_length()));
+        assert!(report.fork_frequency() > 0.0);
+        // Moving first is an advantage, even for random players
+        assert!(report.first_mover_win_rate() > 0.4);
+
+        assert_eq!(
+            report,
+            run_batch(&RandomBot::new(1), &RandomBot::new(2), 200, 42)
+        );
+    }
+
+    #[test]
+    fn test_empty_batch() {
+        let report = run_batch(&RandomBot::new(1), &RandomBot::new(2), 0, 0);
+        assert_eq!(report, BatchReport::default());
+        assert_eq!(report.average_length(), 0.0);
+    }
+
+    /// Standings where `strong` beats `weak` in `wins` games out of 10,
+    /// the rest being drawn
+    fn two_player_standings(wins: usize) -> Standings {
+        let mut standings = Standings::new(&["strong", "weak"]);
+        for game in 0..10 {
+            let state = if game < wins {
+                GameState::Won(Player::Human)
+            } else {
+                GameState::Draw
+            };
+            standings.record_game(0, 1, state);
+        }
+        standings
+    }
+
+    #[test]
+    fn test_ratings_rank_dominant_bot_first() {
+        let ratings = compute_ratings(&two_player_standings(8));
+        assert!(ratings["strong"] > ratings["weak"]);
+        // Ratings move symmetrically around the starting value
+        assert!((ratings["strong"] + ratings["weak"] - 3000.0).abs() < 1e-6);
+
+        // A more lopsided result spreads the ratings further
+        let lopsided = compute_ratings(&two_player_standings(10));
+        assert!(lopsided["strong"] > ratings["strong"]);
+        assert!(lopsided["strong"].is_finite());
+    }
+
+    #[test]
+    fn test_ratings_are_deterministic() {
+        let mut standings = Standings::new(&["a", "b", "c"]);
+        standings.record_game(0, 1, GameState::Won(Player::Human));
+        standings.record_game(1, 2, GameState::Won(Player::Human));
+        standings.record_game(2, 0, GameState::Draw);
+
+        let ratings = compute_ratings(&standings);
+        assert_eq!(ratings, compute_ratings(&standings));
+        assert_eq!(ratings.len(), 3);
+        assert!(ratings["a"] > ratings["c"]);
+
+        // Without games everyone keeps the starting rating
+        let ratings = compute_ratings(&Standings::new(&["x", "y"]));
+        assert_eq!(ratings["x"], 1500.0);
+        assert_eq!(ratings["y"], 1500.0);
+    }
+}
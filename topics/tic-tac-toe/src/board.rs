@@ -1,21 +1,169 @@
+use crate::symmetry::Transform;
 use crate::types::{Cell, Player};
+use std::fmt;
+use std::ops::Index;
+use std::sync::OnceLock;
 
-/// Represents the game board (3x3 grid)
-#[derive(Debug, Clone)]
+/// Largest supported board size (6x6)
+/// Zobrist keys and base-3 position ids (which must fit in a u64) are sized
+/// for it; exhaustive AI search is already impractical well before that
+pub const MAX_SIZE: usize = 6;
+
+/// Number of cells of the largest supported board
+const MAX_CELLS: usize = MAX_SIZE * MAX_SIZE;
+
+/// Zobrist keys, one random value per (position, player) pair
+/// Generated at compile time so the table is initialized exactly once
+const ZOBRIST: [[u64; 2]; MAX_CELLS] = zobrist_table();
+
+/// Builds the Zobrist table using the SplitMix64 generator with a fixed seed
+const fn zobrist_table() -> [[u64; 2]; MAX_CELLS] {
+    let mut table = [[0; 2]; MAX_CELLS];
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut i = 0;
+    while i < 2 * MAX_CELLS {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i / 2][i % 2] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Returns the Zobrist key for a player's mark at the given position
+fn zobrist_key(position: usize, player: Player) -> u64 {
+    match player {
+        Player::Human => ZOBRIST[position][0],
+        Player::AI => ZOBRIST[position][1],
+    }
+}
+
+/// Winning lines of each board size, computed on first use
+static LINES: [OnceLock<Vec<Vec<usize>>>; MAX_SIZE + 1] = [const { OnceLock::new() }; MAX_SIZE + 1];
+
+/// Returns every run of `win_length` cells in a row on a `size` x `size`
+/// board: horizontal runs, then vertical ones, then both diagonal directions
+/// Within each direction, runs are ordered by their first cell
+/// Empty if `win_length` is 0 or larger than `size`
+pub fn winning_lines(size: usize, win_length: usize) -> Vec<Vec<usize>> {
+    if win_length == 0 || win_length > size {
+        return Vec::new();
+    }
+
+    let starts = size - win_length + 1;
+    let run = |row: usize, col: usize, (dr, dc): (usize, isize)| -> Vec<usize> {
+        (0..win_length)
+            .map(|i| (row + i * dr) * size + col.wrapping_add_signed(i as isize * dc))
+            .collect()
+    };
+
+    let rows = (0..size).flat_map(|row| (0..starts).map(move |col| (row, col, (0, 1))));
+    let cols = (0..starts).flat_map(|row| (0..size).map(move |col| (row, col, (1, 0))));
+    let diagonals = (0..starts).flat_map(|row| (0..starts).map(move |col| (row, col, (1, 1))));
+    let anti_diagonals =
+        (0..starts).flat_map(|row| (win_length - 1..size).map(move |col| (row, col, (1, -1))));
+
+    rows.chain(cols)
+        .chain(diagonals)
+        .chain(anti_diagonals)
+        .map(|(row, col, step)| run(row, col, step))
+        .collect()
+}
+
+/// Reasons a board of the requested size or shape can't be created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardError {
+    /// A board needs at least one row and one column
+    ZeroSize,
+    /// The size is above `MAX_SIZE`
+    TooLarge { size: usize, max: usize },
+    /// A grid row (0-based) doesn't have one cell per row of the grid
+    NotSquare { row: usize, len: usize },
+    /// Two boards being compared don't have the same size
+    SizeMismatch { left: usize, right: usize },
+    /// A win length of 0, or longer than the board is wide
+    InvalidWinLength { win_length: usize, size: usize },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::ZeroSize => write!(f, "board size must be at least 1"),
+            BoardError::TooLarge { size, max } => {
+                write!(f, "board size {size} is larger than the maximum of {max}")
+            }
+            BoardError::NotSquare { row, len } => {
+                write!(f, "row {row} has {len} cells, so the grid is not square")
+            }
+            BoardError::SizeMismatch { left, right } => {
+                write!(
+                    f,
+                    "can't compare a {left}x{left} board with a {right}x{right} one"
+                )
+            }
+            BoardError::InvalidWinLength { win_length, size } => {
+                write!(
+                    f,
+                    "{win_length} in a row can't be won on a {size}x{size} board"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// Represents the game board (a square grid, 3x3 by default)
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board {
-    /// Internal representation as a 1D array of 9 cells
-    cells: [Cell; 9],
+    /// Number of rows (and columns)
+    size: usize,
+    /// Internal representation as a 1D array of size * size cells, row by row
+    cells: Vec<Cell>,
+    /// Zobrist hash of the cells, updated incrementally on each move
+    hash: u64,
+    /// Positions of the empty cells in increasing order, updated on each
+    /// move so the search doesn't rescan the board for its moves
+    empty: Vec<usize>,
 }
 
 impl Board {
-    /// Creates a new empty board
+    /// Creates a new empty 3x3 board
     pub fn new() -> Self {
+        Self::empty(3)
+    }
+
+    /// Creates a new empty `size` x `size` board
+    /// Sizes from 1 to `MAX_SIZE` are supported
+    pub fn with_size(size: usize) -> Result<Self, BoardError> {
+        match size {
+            0 => Err(BoardError::ZeroSize),
+            1..=MAX_SIZE => Ok(Self::empty(size)),
+            _ => Err(BoardError::TooLarge {
+                size,
+                max: MAX_SIZE,
+            }),
+        }
+    }
+
+    /// Creates an empty board, the size having been checked by the caller
+    fn empty(size: usize) -> Self {
         Board {
-            cells: [Cell::Empty; 9],
+            size,
+            cells: vec![Cell::Empty; size * size],
+            hash: 0,
+            empty: (0..size * size).collect(),
         }
     }
 
-    /// Returns the cell at the given position (0-8)
+    /// Returns the number of rows (and columns) of the board
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the cell at the given position (0-8 on a 3x3 board)
     pub fn get(&self, position: usize) -> Option<Cell> {
         self.cells.get(position).copied()
     }
@@ -23,61 +171,1121 @@ impl Board {
     /// Places a player's mark at the given position
     /// Returns true if the move was successful, false otherwise
     pub fn make_move(&mut self, position: usize, player: Player) -> bool {
-        if position >= 9 {
+        if position >= self.cells.len() {
             return false;
         }
 
         if self.cells[position].is_empty() {
             self.cells[position] = Cell::Occupied(player);
+            self.hash ^= zobrist_key(position, player);
+            if let Ok(idx) = self.empty.binary_search(&position) {
+                self.empty.remove(idx);
+            }
             true
         } else {
             false
         }
     }
 
+    /// Removes the mark at the given position
+    /// Returns true if a mark was removed, false if the cell was empty or invalid
+    pub fn undo_move(&mut self, position: usize) -> bool {
+        match self.get(position) {
+            Some(Cell::Occupied(player)) => {
+                self.cells[position] = Cell::Empty;
+                self.hash ^= zobrist_key(position, player);
+                if let Err(idx) = self.empty.binary_search(&position) {
+                    self.empty.insert(idx, position);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the Zobrist hash of the current position
+    /// Suitable as a transposition table key; maintained in O(1) per move
+    pub fn hash_key(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns a compact id of the position: the cells read as a base-3
+    /// number (empty 0, X 1, O 2) with cell 0 as the most significant digit
+    pub fn position_id(&self) -> u64 {
+        self.cells.iter().fold(0, |id, cell| {
+            id * 3
+                + match cell {
+                    Cell::Empty => 0,
+                    Cell::Occupied(Player::Human) => 1,
+                    Cell::Occupied(Player::AI) => 2,
+                }
+        })
+    }
+
     /// Returns a list of all available moves (empty cell positions)
     pub fn available_moves(&self) -> Vec<usize> {
+        self.empty.clone()
+    }
+
+    /// Returns the available moves without copying them, in order
+    pub fn available_moves_slice(&self) -> &[usize] {
+        &self.empty
+    }
+
+    /// Returns the positions of all cells owned by `player`, in order
+    pub fn cells_of(&self, player: Player) -> Vec<usize> {
         self.cells
             .iter()
             .enumerate()
-            .filter(|(_, cell)| cell.is_empty())
+            .filter(|(_, cell)| **cell == Cell::Occupied(player))
             .map(|(idx, _)| idx)
             .collect()
     }
 
+    /// Returns the cells owned by `player` as a bitmask, bit i being set if
+    /// position i holds their mark
+    /// A u64 covers every board up to `MAX_SIZE`
+    pub fn occupancy(&self, player: Player) -> u64 {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| **cell == Cell::Occupied(player))
+            .fold(0, |mask, (idx, _)| mask | 1 << idx)
+    }
+
+    /// Returns the number of marks of each side as (human, ai)
+    pub fn counts(&self) -> (usize, usize) {
+        self.cells
+            .iter()
+            .fold((0, 0), |(human, ai), cell| match cell {
+                Cell::Occupied(Player::Human) => (human + 1, ai),
+                Cell::Occupied(Player::AI) => (human, ai + 1),
+                Cell::Empty => (human, ai),
+            })
+    }
+
+    /// Returns the positions of all empty cells, in order
+    /// Same as `available_moves`, named for rendering and analysis code
+    pub fn empty_cells(&self) -> Vec<usize> {
+        self.available_moves()
+    }
+
     /// Returns true if the board is full (no available moves)
     pub fn is_full(&self) -> bool {
-        self.cells.iter().all(|cell| !cell.is_empty())
+        self.empty.is_empty()
+    }
+
+    /// Returns true if no cell is occupied yet (the board still has all
+    /// its cells, they are just empty)
+    pub fn is_empty(&self) -> bool {
+        self.empty.len() == self.cells.len()
     }
 
     /// Display the board
     pub fn display(&self) {
         println!("\n");
-        for row in 0..3 {
-            print!(" ");
-            for col in 0..3 {
-                let idx = row * 3 + col;
-                print!(" {} ", self.cells[idx].symbol());
-                if col < 2 {
-                    print!("|");
+        print!("{}", self.display_with_glyphs(&GlyphSet::default()));
+        println!("\n");
+    }
+
+    /// Renders the board as a grid using the given glyphs for the marks
+    pub fn display_with_glyphs(&self, glyphs: &GlyphSet) -> String {
+        self.render(glyphs, None)
+    }
+
+    /// Renders the board with the cell at `position` in brackets, e.g. `[X]`,
+    /// to show which move was just played
+    /// Columns stay aligned; an out-of-range position highlights nothing
+    pub fn display_highlight(&self, position: usize) -> String {
+        self.render(&GlyphSet::default(), Some(position))
+    }
+
+    /// Draws the grid, bracketing the `highlight` cell instead of padding it
+    fn render(&self, glyphs: &GlyphSet, highlight: Option<usize>) -> String {
+        let mut out = String::new();
+        let size = self.size;
+        for row in 0..size {
+            out.push(' ');
+            for col in 0..size {
+                let idx = row * size + col;
+                let glyph = glyphs.glyph(self.cells[idx]);
+                if highlight == Some(idx) {
+                    out.push_str(&format!("[{glyph}]"));
+                } else {
+                    out.push_str(&format!(" {glyph} "));
+                }
+                if col < size - 1 {
+                    out.push('|');
                 }
             }
-            println!();
-            if row < 2 {
-                println!(" -----------");
+            out.push('\n');
+            if row < size - 1 {
+                out.push_str(&format!(" {}\n", self.separator()));
             }
         }
-        println!("\n");
+        out
+    }
+
+    /// Renders the board mirrored top-bottom, so the first row is drawn last
+    /// For UIs that index rows from the bottom
+    pub fn display_flipped_vertical(&self) -> String {
+        Transform::FlipVertical
+            .apply(self)
+            .display_with_glyphs(&GlyphSet::default())
+    }
+
+    /// Renders the board mirrored left-right
+    pub fn display_flipped_horizontal(&self) -> String {
+        Transform::FlipHorizontal
+            .apply(self)
+            .display_with_glyphs(&GlyphSet::default())
+    }
+
+    /// Renders the board with column letters and row numbers, chess style
+    /// Each occupied cell is then listed by its label, e.g. "b2: X"
+    pub fn display_labeled(&self) -> String {
+        let size = self.size;
+        let letters: Vec<String> = (0..size)
+            .map(|col| column_letter(col).to_string())
+            .collect();
+        let mut out = format!("   {}\n", letters.join("   "));
+        for row in 0..size {
+            out.push_str(&format!("{} ", row + 1));
+            for col in 0..size {
+                let idx = row * size + col;
+                out.push_str(&format!(" {} ", self.cells[idx].symbol()));
+                if col < size - 1 {
+                    out.push('|');
+                }
+            }
+            out.push('\n');
+            if row < size - 1 {
+                out.push_str(&format!("  {}\n", self.separator()));
+            }
+        }
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            if let Cell::Occupied(player) = cell {
+                let column = column_letter(idx % size);
+                out.push_str(&format!(
+                    "{}{}: {}\n",
+                    column,
+                    idx / size + 1,
+                    player.symbol()
+                ));
+            }
+        }
+
+        out
+    }
+
+    /// Returns the horizontal rule drawn between two rows
+    fn separator(&self) -> String {
+        "-".repeat(4 * self.size - 1)
+    }
+
+    /// Returns the position at the given 0-indexed row and column
+    /// Returns None if either is off the board
+    pub fn index_at(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.size && col < self.size).then_some(row * self.size + col)
+    }
+
+    /// Converts a position number as shown to players (1 to size * size)
+    /// into a 0-indexed position
+    /// Returns None if the number is off the board
+    pub fn from_display_index(&self, n: usize) -> Option<usize> {
+        n.checked_sub(1)
+            .filter(|&position| position < self.cells.len())
+    }
+
+    /// Converts a 0-indexed position into the number shown to players
+    pub fn to_display_index(&self, position: usize) -> usize {
+        position + 1
+    }
+
+    /// Returns the 0-indexed (row, column) of a position
+    /// Returns None if the position is off the board
+    pub fn coordinates(&self, position: usize) -> Option<(usize, usize)> {
+        (position < self.cells.len()).then_some((position / self.size, position % self.size))
+    }
+
+    /// Describes a position in words, e.g. "center", "top-left corner" or
+    /// "middle-right edge" on a 3x3 board
+    /// Other sizes use 1-indexed "(row, col)" coordinates, as typed in the CLI
+    pub fn describe_move(&self, position: usize) -> String {
+        let Some((row, col)) = self.coordinates(position) else {
+            return String::from("off the board");
+        };
+        if self.size != 3 {
+            return format!("({}, {})", row + 1, col + 1);
+        }
+
+        const ROWS: [&str; 3] = ["top", "middle", "bottom"];
+        const COLS: [&str; 3] = ["left", "middle", "right"];
+        match (row, col) {
+            (1, 1) => String::from("center"),
+            (1, _) | (_, 1) => format!("{}-{} edge", ROWS[row], COLS[col]),
+            _ => format!("{}-{} corner", ROWS[row], COLS[col]),
+        }
+    }
+
+    /// Returns the center cell
+    /// Even-sized boards have no single center cell and return None
+    pub fn center(&self) -> Option<Cell> {
+        (self.size % 2 == 1).then(|| self.cells[self.cells.len() / 2])
+    }
+
+    /// Returns the four corner cells: top-left, top-right, bottom-left, bottom-right
+    pub fn corners(&self) -> [Cell; 4] {
+        let last = self.size - 1;
+        [
+            self.cells[0],
+            self.cells[last],
+            self.cells[last * self.size],
+            self.cells[last * self.size + last],
+        ]
+    }
+
+    /// Returns the cells of the main diagonal, from top-left to bottom-right
+    pub fn diagonal(&self) -> Vec<Cell> {
+        (0..self.size)
+            .map(|i| self.cells[i * self.size + i])
+            .collect()
+    }
+
+    /// Returns the cells of the anti-diagonal, from top-right to bottom-left
+    pub fn anti_diagonal(&self) -> Vec<Cell> {
+        (0..self.size)
+            .map(|i| self.cells[i * self.size + self.size - 1 - i])
+            .collect()
+    }
+
+    /// Returns every line a player can win by owning, as position indices in
+    /// scan order: rows, then columns, then the two diagonals
+    /// A line spans the whole board, so there are `2 * size + 2` of them
+    /// (8 on 3x3); they are computed once per size and shared
+    pub fn lines(&self) -> &'static [Vec<usize>] {
+        LINES[self.size].get_or_init(|| winning_lines(self.size, self.size))
+    }
+
+    /// Returns every position whose cell differs between `self` and
+    /// `other`, as (position, old cell, new cell), in increasing order
+    /// Boards of different sizes can't be compared and give an empty diff
+    pub fn diff(&self, other: &Board) -> Vec<(usize, Cell, Cell)> {
+        if self.size != other.size {
+            return Vec::new();
+        }
+
+        self.cells
+            .iter()
+            .zip(&other.cells)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(position, (&old, &new))| (position, old, new))
+            .collect()
+    }
+
+    /// Returns the number of cells that differ between `self` and `other`
+    /// Fails if the boards don't have the same size
+    pub fn distance(&self, other: &Board) -> Result<usize, BoardError> {
+        if self.size != other.size {
+            return Err(BoardError::SizeMismatch {
+                left: self.size,
+                right: other.size,
+            });
+        }
+
+        Ok(self
+            .cells
+            .iter()
+            .zip(&other.cells)
+            .filter(|(a, b)| a != b)
+            .count())
+    }
+
+    /// Returns every non-identity transform that maps the board onto itself,
+    /// in the order of `Transform::ALL`
+    /// An empty board has all 7; a board with none is fully asymmetric
+    pub fn self_symmetries(&self) -> Vec<Transform> {
+        Transform::ALL
+            .into_iter()
+            .filter(|&transform| transform != Transform::Identity && transform.apply(self) == *self)
+            .collect()
+    }
+
+    /// Returns the cells as rows, top to bottom, each from left to right
+    pub fn as_grid(&self) -> Vec<Vec<Cell>> {
+        self.cells.chunks(self.size).map(<[Cell]>::to_vec).collect()
+    }
+
+    /// Creates a board from rows of cells laid out as by `as_grid`
+    /// The grid must be square, with a size accepted by `with_size`
+    pub fn from_grid(grid: &[&[Cell]]) -> Result<Board, BoardError> {
+        let mut board = Board::with_size(grid.len())?;
+
+        for (row, cells) in grid.iter().enumerate() {
+            if cells.len() != board.size {
+                return Err(BoardError::NotSquare {
+                    row,
+                    len: cells.len(),
+                });
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                if let Cell::Occupied(player) = cell {
+                    board.make_move(row * board.size + col, *player);
+                }
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Returns each cell's symbol ('X', 'O' or ' ') in the layout of `as_grid`
+    /// Meant for frontends drawing the board themselves
+    pub fn char_grid(&self) -> Vec<Vec<char>> {
+        self.cells
+            .chunks(self.size)
+            .map(|row| row.iter().map(Cell::symbol).collect())
+            .collect()
+    }
+
+    /// Renders the board as an HTML `<table>`, one row per board row
+    /// Each cell is classed by owner (`cell-x`, `cell-o` or `cell-empty`)
+    /// so a page can style it with CSS
+    #[cfg(feature = "html")]
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<table class=\"board\">\n");
+        for row in self.cells.chunks(self.size) {
+            out.push_str("<tr>");
+            for cell in row {
+                let (class, text) = match cell {
+                    Cell::Occupied(Player::Human) => ("cell-x", "X"),
+                    Cell::Occupied(Player::AI) => ("cell-o", "O"),
+                    Cell::Empty => ("cell-empty", ""),
+                };
+                out.push_str(&format!("<td class=\"{class}\">{text}</td>"));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out
     }
 
     /// Returns the internal cells array (for testing purposes)
-    pub fn cells(&self) -> &[Cell; 9] {
+    pub fn cells(&self) -> &[Cell] {
         &self.cells
     }
 }
 
+/// Returns the letter labeling a 0-indexed column: 'a', 'b', 'c', ...
+fn column_letter(col: usize) -> char {
+    (b'a' + col as u8) as char
+}
+
 impl Default for Board {
     fn default() -> Self {
         Self::new()
     }
 }
+
+impl Index<usize> for Board {
+    type Output = Cell;
+
+    /// Returns the cell at the given position
+    /// Panics if the position is out of range, like slice indexing;
+    /// use `get` for fallible access
+    fn index(&self, position: usize) -> &Cell {
+        &self.cells[position]
+    }
+}
+
+/// Characters used to draw each kind of cell
+///
+/// Shape-based glyphs keep the marks distinguishable without relying on
+/// color. The default is the classic 'X' / 'O'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphSet {
+    /// Glyph for the Human's marks
+    pub human: char,
+    /// Glyph for the AI's marks
+    pub ai: char,
+    /// Glyph for empty cells
+    pub empty: char,
+}
+
+impl GlyphSet {
+    /// Classic letters: X and O
+    pub const CLASSIC: GlyphSet = GlyphSet {
+        human: 'X',
+        ai: 'O',
+        empty: ' ',
+    };
+
+    /// Distinct geometric shapes: ✕ and ◯
+    pub const SHAPES: GlyphSet = GlyphSet {
+        human: '✕',
+        ai: '◯',
+        empty: ' ',
+    };
+
+    /// Plain ASCII blocks: # and =
+    pub const BLOCKS: GlyphSet = GlyphSet {
+        human: '#',
+        ai: '=',
+        empty: ' ',
+    };
+
+    /// Returns the glyph for a cell
+    pub fn glyph(&self, cell: Cell) -> char {
+        match cell {
+            Cell::Empty => self.empty,
+            Cell::Occupied(Player::Human) => self.human,
+            Cell::Occupied(Player::AI) => self.ai,
+        }
+    }
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        GlyphSet::CLASSIC
+    }
+}
+
+/// Fluent builder for setting up board positions directly
+///
+/// Marks are placed without enforcing turn alternation, which makes it
+/// convenient for tests and puzzles. Placing on an occupied or invalid
+/// position is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    /// Starts from an empty board
+    pub fn new() -> Self {
+        BoardBuilder {
+            board: Board::new(),
+        }
+    }
+
+    /// Places an X (Human) mark at the given position
+    pub fn x(self, position: usize) -> Self {
+        self.mark(position, Player::Human)
+    }
+
+    /// Places an O (AI) mark at the given position
+    pub fn o(self, position: usize) -> Self {
+        self.mark(position, Player::AI)
+    }
+
+    /// Places the given player's mark at the given position
+    pub fn mark(mut self, position: usize, player: Player) -> Self {
+        self.board.make_move(position, player);
+        self
+    }
+
+    /// Returns the constructed board
+    pub fn build(self) -> Board {
+        self.board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recomputes the Zobrist hash from scratch by scanning every cell
+    fn full_hash(board: &Board) -> u64 {
+        board
+            .cells()
+            .iter()
+            .enumerate()
+            .fold(0, |hash, (idx, cell)| match cell {
+                Cell::Occupied(player) => hash ^ zobrist_key(idx, *player),
+                Cell::Empty => hash,
+            })
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines = Board::new().lines();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(
+            lines,
+            [
+                [0, 1, 2],
+                [3, 4, 5],
+                [6, 7, 8],
+                [0, 3, 6],
+                [1, 4, 7],
+                [2, 5, 8],
+                [0, 4, 8],
+                [2, 4, 6],
+            ]
+        );
+        assert_eq!(lines, winning_lines(3, 3));
+    }
+
+    #[test]
+    fn test_winning_lines() {
+        assert_eq!(
+            winning_lines(3, 3),
+            vec![
+                vec![0, 1, 2],
+                vec![3, 4, 5],
+                vec![6, 7, 8],
+                vec![0, 3, 6],
+                vec![1, 4, 7],
+                vec![2, 5, 8],
+                vec![0, 4, 8],
+                vec![2, 4, 6],
+            ]
+        );
+
+        // 4x4 with three in a row: 8 runs in each straight direction and
+        // 4 in each diagonal one
+        let lines = winning_lines(4, 3);
+        assert_eq!(lines.len(), 24);
+        assert_eq!(lines[1], vec![1, 2, 3]);
+        assert_eq!(lines[8], vec![0, 4, 8]);
+        assert!(lines.contains(&vec![5, 10, 15]));
+        assert!(lines.contains(&vec![7, 10, 13]));
+
+        assert!(winning_lines(3, 4).is_empty());
+        assert!(winning_lines(3, 0).is_empty());
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let board = BoardBuilder::new().x(0).o(4).x(6).build();
+        let x = Cell::Occupied(Player::Human);
+        let o = Cell::Occupied(Player::AI);
+        let e = Cell::Empty;
+
+        // [0, 4, 8] and [2, 4, 6]
+        assert_eq!(board.diagonal(), vec![x, o, e]);
+        assert_eq!(board.anti_diagonal(), vec![e, o, x]);
+    }
+
+    #[test]
+    fn test_diagonals_on_larger_board() {
+        let mut board = Board::with_size(4).unwrap();
+        board.make_move(5, Player::Human);
+        board.make_move(15, Player::AI);
+        board.make_move(3, Player::Human);
+        board.make_move(9, Player::AI);
+        let x = Cell::Occupied(Player::Human);
+        let o = Cell::Occupied(Player::AI);
+        let e = Cell::Empty;
+
+        // [0, 5, 10, 15] and [3, 6, 9, 12]
+        assert_eq!(board.diagonal(), vec![e, x, e, o]);
+        assert_eq!(board.anti_diagonal(), vec![x, e, o, e]);
+    }
+
+    #[test]
+    fn test_grids() {
+        let board = BoardBuilder::new().x(0).o(4).x(5).build();
+        let x = Cell::Occupied(Player::Human);
+        let o = Cell::Occupied(Player::AI);
+        let e = Cell::Empty;
+
+        assert_eq!(
+            board.as_grid(),
+            vec![vec![x, e, e], vec![e, o, x], vec![e, e, e]]
+        );
+        assert_eq!(
+            board.char_grid(),
+            vec![
+                vec!['X', ' ', ' '],
+                vec![' ', 'O', 'X'],
+                vec![' ', ' ', ' ']
+            ]
+        );
+
+        let board = Board::with_size(4).unwrap();
+        assert_eq!(board.char_grid(), vec![vec![' '; 4]; 4]);
+    }
+
+    #[test]
+    fn test_with_size() {
+        let board = Board::with_size(4).unwrap();
+        assert_eq!(board.size(), 4);
+        assert_eq!(board.available_moves().len(), 16);
+        assert_eq!(Board::with_size(3), Ok(Board::new()));
+        assert_eq!(Board::with_size(MAX_SIZE).unwrap().cells().len(), 36);
+    }
+
+    #[test]
+    fn test_with_size_rejects_zero() {
+        assert_eq!(Board::with_size(0), Err(BoardError::ZeroSize));
+    }
+
+    #[test]
+    fn test_with_size_rejects_oversized() {
+        assert_eq!(
+            Board::with_size(MAX_SIZE + 1),
+            Err(BoardError::TooLarge { size: 7, max: 6 })
+        );
+        assert!(Board::with_size(1000).is_err());
+        assert_eq!(
+            BoardError::TooLarge { size: 7, max: 6 }.to_string(),
+            "board size 7 is larger than the maximum of 6"
+        );
+    }
+
+    #[test]
+    fn test_sized_board_geometry() {
+        let mut board = Board::with_size(4).unwrap();
+        assert!(board.make_move(15, Player::Human));
+        assert!(!board.make_move(16, Player::AI));
+
+        assert_eq!(board.index_at(3, 3), Some(15));
+        assert_eq!(board.index_at(4, 0), None);
+        assert_eq!(board.coordinates(6), Some((1, 2)));
+        assert_eq!(board.center(), None);
+        assert_eq!(board.corners()[3], Cell::Occupied(Player::Human));
+        assert_eq!(board.lines().len(), 10);
+        assert_eq!(board.lines()[9], vec![3, 6, 9, 12]);
+
+        let rendered = board.display_with_glyphs(&GlyphSet::default());
+        assert_eq!(rendered.lines().nth(1), Some(" ---------------"));
+        assert_eq!(rendered.lines().last(), Some("    |   |   | X "));
+        assert!(board.display_labeled().contains("d4: X"));
+    }
+
+    #[test]
+    fn test_builder_places_marks() {
+        let board = BoardBuilder::new().x(0).o(4).x(1).build();
+
+        let x = Cell::Occupied(Player::Human);
+        let o = Cell::Occupied(Player::AI);
+        let e = Cell::Empty;
+        assert_eq!(board.cells(), &[x, x, e, e, o, e, e, e, e]);
+    }
+
+    #[test]
+    fn test_builder_ignores_alternation() {
+        // Three X marks in a row, no O at all
+        let board = BoardBuilder::new().x(0).x(1).x(2).build();
+        assert_eq!(board.available_moves(), vec![3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(Board::new().is_empty());
+        assert!(Board::with_size(4).unwrap().is_empty());
+
+        let mut board = Board::new();
+        board.make_move(8, Player::AI);
+        assert!(!board.is_empty());
+        assert!(!board.is_full());
+
+        board.undo_move(8);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_cells_by_owner() {
+        let mut board = Board::new();
+        board.make_move(4, Player::Human);
+        board.make_move(0, Player::AI);
+        board.make_move(8, Player::Human);
+        board.make_move(2, Player::AI);
+        board.make_move(1, Player::Human);
+
+        assert_eq!(board.cells_of(Player::Human), vec![1, 4, 8]);
+        assert_eq!(board.cells_of(Player::AI), vec![0, 2]);
+        assert_eq!(board.empty_cells(), vec![3, 5, 6, 7]);
+        assert_eq!(board.empty_cells(), board.available_moves());
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = BoardBuilder::new().x(0).o(4).build();
+        let mut after = before.clone();
+        after.make_move(8, Player::Human);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![(8, Cell::Empty, Cell::Occupied(Player::Human))]
+        );
+        assert_eq!(
+            after.diff(&before),
+            vec![(8, Cell::Occupied(Player::Human), Cell::Empty)]
+        );
+        assert!(before.diff(&before).is_empty());
+
+        let larger = Board::with_size(4).unwrap();
+        assert!(before.diff(&larger).is_empty());
+    }
+
+    #[test]
+    fn test_distance() {
+        let board = BoardBuilder::new().x(0).o(4).x(8).build();
+        assert_eq!(board.distance(&board), Ok(0));
+
+        // An added mark, a removed one and a changed owner
+        let other = BoardBuilder::new().o(0).o(4).x(2).build();
+        assert_eq!(board.distance(&other), Ok(3));
+        assert_eq!(other.distance(&board), Ok(3));
+
+        let larger = Board::with_size(4).unwrap();
+        assert_eq!(
+            board.distance(&larger),
+            Err(BoardError::SizeMismatch { left: 3, right: 4 })
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        assert_eq!(Board::new().counts(), (0, 0));
+
+        let mut board = Board::new();
+        board.make_move(4, Player::Human);
+        board.make_move(0, Player::AI);
+        board.make_move(8, Player::Human);
+        assert_eq!(board.counts(), (2, 1));
+
+        board.undo_move(8);
+        assert_eq!(board.counts(), (1, 1));
+    }
+
+    #[test]
+    fn test_cells_by_owner_empty_board() {
+        let board = Board::new();
+        assert!(board.cells_of(Player::Human).is_empty());
+        assert!(board.cells_of(Player::AI).is_empty());
+        assert_eq!(board.empty_cells().len(), 9);
+    }
+
+    #[test]
+    fn test_display_labeled() {
+        let board = BoardBuilder::new().x(4).o(2).build();
+        let labeled = board.display_labeled();
+        let lines: Vec<&str> = labeled.lines().collect();
+
+        assert_eq!(lines[0], "   a   b   c");
+        assert_eq!(lines[1], "1    |   | O ");
+        assert_eq!(lines[3], "2    | X |   ");
+        assert_eq!(lines[5], "3    |   |   ");
+        // The O in column c sits under the "c" header
+        assert_eq!(lines[1].find('O'), lines[0].find('c'));
+        assert!(labeled.contains("c1: O"));
+        assert!(labeled.contains("b2: X"));
+    }
+
+    #[test]
+    fn test_position_id() {
+        assert_eq!(Board::new().position_id(), 0);
+        assert_eq!(BoardBuilder::new().x(8).build().position_id(), 1);
+        assert_eq!(BoardBuilder::new().o(7).build().position_id(), 6);
+        assert_eq!(BoardBuilder::new().x(0).build().position_id(), 3u64.pow(8));
+    }
+
+    #[test]
+    fn test_display_with_glyphs() {
+        let board = BoardBuilder::new().x(0).o(4).build();
+
+        let classic = board.display_with_glyphs(&GlyphSet::default());
+        assert_eq!(
+            classic,
+            "  X |   |   \n -----------\n    | O |   \n -----------\n    |   |   \n"
+        );
+
+        let custom = GlyphSet {
+            human: '#',
+            ai: '=',
+            empty: '.',
+        };
+        let rendered = board.display_with_glyphs(&custom);
+        assert!(rendered.starts_with("  # | . | . "));
+        assert!(rendered.contains(" . | = | . "));
+        assert!(!rendered.contains('X') && !rendered.contains('O'));
+
+        let shapes = board.display_with_glyphs(&GlyphSet::SHAPES);
+        assert!(shapes.contains('✕') && shapes.contains('◯'));
+    }
+
+    #[test]
+    fn test_display_highlight() {
+        let board = BoardBuilder::new().x(0).o(4).build();
+
+        let rendered = board.display_highlight(4);
+        assert_eq!(
+            rendered,
+            "  X |   |   \n -----------\n    |[O]|   \n -----------\n    |   |   \n"
+        );
+        assert_eq!(rendered.matches('[').count(), 1);
+        assert!(!rendered.contains("[X]"));
+
+        // Every row keeps the same width
+        let widths: Vec<usize> = rendered.lines().map(|line| line.chars().count()).collect();
+        assert!(widths.iter().all(|&width| width == widths[0]));
+
+        assert_eq!(
+            board.display_highlight(9),
+            board.display_with_glyphs(&GlyphSet::default())
+        );
+    }
+
+    #[test]
+    fn test_display_flipped() {
+        let board = BoardBuilder::new().x(0).o(5).build();
+
+        assert_eq!(
+            board.display_flipped_vertical(),
+            "    |   |   \n -----------\n    |   | O \n -----------\n  X |   |   \n"
+        );
+        assert_eq!(
+            board.display_flipped_horizontal(),
+            "    |   | X \n -----------\n  O |   |   \n -----------\n    |   |   \n"
+        );
+
+        // Flipping twice gives back the original rendering
+        let original = board.display_with_glyphs(&GlyphSet::default());
+        let vertical = Transform::FlipVertical.apply(&board);
+        let horizontal = Transform::FlipHorizontal.apply(&board);
+        assert_eq!(vertical.display_flipped_vertical(), original);
+        assert_eq!(horizontal.display_flipped_horizontal(), original);
+    }
+
+    #[test]
+    fn test_index() {
+        let board = BoardBuilder::new().x(0).o(4).build();
+        assert_eq!(board[0], Cell::Occupied(Player::Human));
+        assert_eq!(board[4], Cell::Occupied(Player::AI));
+        assert_eq!(board[8], Cell::Empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let board = Board::new();
+        let _ = board[9];
+    }
+
+    #[test]
+    fn test_coordinates() {
+        let board = Board::new();
+        assert_eq!(board.index_at(0, 0), Some(0));
+        assert_eq!(board.index_at(1, 2), Some(5));
+        assert_eq!(board.index_at(3, 0), None);
+        assert_eq!(board.coordinates(5), Some((1, 2)));
+        assert_eq!(board.coordinates(9), None);
+    }
+
+    #[test]
+    fn test_describe_move() {
+        let board = Board::new();
+        assert_eq!(board.describe_move(0), "top-left corner");
+        assert_eq!(board.describe_move(4), "center");
+        assert_eq!(board.describe_move(5), "middle-right edge");
+        assert_eq!(board.describe_move(7), "bottom-middle edge");
+        assert_eq!(board.describe_move(8), "bottom-right corner");
+        assert_eq!(board.describe_move(9), "off the board");
+
+        let board = Board::with_size(4).unwrap();
+        assert_eq!(board.describe_move(6), "(2, 3)");
+    }
+
+    #[test]
+    fn test_center_and_corners() {
+        let board = BoardBuilder::new().o(4).x(0).x(8).o(2).x(1).build();
+
+        let x = Cell::Occupied(Player::Human);
+        let o = Cell::Occupied(Player::AI);
+        assert_eq!(board.center(), Some(o));
+        assert_eq!(board.corners(), [x, o, Cell::Empty, x]);
+    }
+
+    #[test]
+    fn test_center_and_corners_empty_board() {
+        let board = Board::new();
+        assert_eq!(board.center(), Some(Cell::Empty));
+        assert_eq!(board.corners(), [Cell::Empty; 4]);
+    }
+
+    #[test]
+    fn test_incremental_hash_matches_recomputation() {
+        let mut board = Board::new();
+        assert_eq!(board.hash_key(), 0);
+
+        for (position, player) in [
+            (4, Player::Human),
+            (0, Player::AI),
+            (8, Player::Human),
+            (2, Player::AI),
+        ] {
+            board.make_move(position, player);
+            assert_eq!(board.hash_key(), full_hash(&board));
+        }
+
+        // Undoing a move restores the previous hash
+        let before = BoardBuilder::new().x(4).o(0).x(8).build().hash_key();
+        board.undo_move(2);
+        assert_eq!(board.hash_key(), full_hash(&board));
+        assert_eq!(board.hash_key(), before);
+    }
+
+    #[test]
+    fn test_available_moves_match_scan() {
+        // Positions of the empty cells, found by scanning every cell
+        let scan = |board: &Board| -> Vec<usize> {
+            (0..board.cells().len())
+                .filter(|&position| board[position].is_empty())
+                .collect()
+        };
+
+        let mut board = Board::with_size(4).unwrap();
+        assert_eq!(board.available_moves(), scan(&board));
+
+        for (position, player) in [
+            (5, Player::Human),
+            (0, Player::AI),
+            (15, Player::Human),
+            (9, Player::AI),
+        ] {
+            board.make_move(position, player);
+            assert_eq!(board.available_moves(), scan(&board));
+        }
+
+        // Rejected moves and undos of empty cells leave the set alone
+        assert!(!board.make_move(5, Player::AI));
+        assert!(!board.make_move(16, Player::AI));
+        assert!(!board.undo_move(1));
+        assert_eq!(board.available_moves_slice(), scan(&board).as_slice());
+
+        for position in [0, 15] {
+            board.undo_move(position);
+            assert_eq!(board.available_moves(), scan(&board));
+        }
+        board.make_move(0, Player::Human);
+        assert_eq!(board.available_moves(), scan(&board));
+        assert_eq!(board.available_moves().len(), 13);
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_to_html() {
+        let board = BoardBuilder::new().x(0).o(4).x(8).build();
+        let html = board.to_html();
+
+        assert!(html.starts_with("<table class=\"board\">"));
+        assert_eq!(html.matches("<tr>").count(), 3);
+        assert_eq!(html.matches("<td ").count(), 9);
+        assert_eq!(html.matches("<td class=\"cell-x\">X</td>").count(), 2);
+        assert_eq!(html.matches("<td class=\"cell-o\">O</td>").count(), 1);
+        assert_eq!(html.matches("<td class=\"cell-empty\"></td>").count(), 6);
+        // Cells appear row by row
+        assert!(html.contains(
+            "<tr><td class=\"cell-empty\"></td><td class=\"cell-o\">O</td><td class=\"cell-empty\"></td></tr>"
+        ));
+    }
+
+    #[test]
+    fn test_display_index_conversion() {
+        let board = Board::new();
+        assert_eq!(board.from_display_index(1), Some(0));
+        assert_eq!(board.from_display_index(9), Some(8));
+        assert_eq!(board.from_display_index(0), None);
+        assert_eq!(board.from_display_index(10), None);
+        assert_eq!(board.to_display_index(0), 1);
+        assert_eq!(board.to_display_index(8), 9);
+
+        // The range follows the board size, and both conversions round-trip
+        let board = Board::with_size(4).unwrap();
+        assert_eq!(board.from_display_index(16), Some(15));
+        assert_eq!(board.from_display_index(17), None);
+        for position in 0..16 {
+            assert_eq!(
+                board.from_display_index(board.to_display_index(position)),
+                Some(position)
+            );
+        }
+    }
+
+    #[test]
+    fn test_occupancy() {
+        let board = BoardBuilder::new().x(0).x(4).x(8).o(2).o(6).build();
+        assert_eq!(board.occupancy(Player::Human), 0b1_0001_0001);
+        assert_eq!(board.occupancy(Player::AI), 0b0_0100_0100);
+        // No cell is owned by both sides
+        assert_eq!(
+            board.occupancy(Player::Human) & board.occupancy(Player::AI),
+            0
+        );
+        assert_eq!(Board::new().occupancy(Player::Human), 0);
+
+        // The last cell of the largest board fits in the mask
+        let mut board = Board::with_size(MAX_SIZE).unwrap();
+        board.make_move(35, Player::AI);
+        assert_eq!(board.occupancy(Player::AI), 1 << 35);
+    }
+
+    #[test]
+    fn test_hash_depends_on_owner() {
+        let x = BoardBuilder::new().x(4).build();
+        let o = BoardBuilder::new().o(4).build();
+        assert_ne!(x.hash_key(), o.hash_key());
+    }
+
+    #[test]
+    fn test_self_symmetries() {
+        assert_eq!(Board::new().self_symmetries(), &Transform::ALL[1..]);
+        // A lone corner still lies on the diagonal it is mirrored along
+        assert_eq!(
+            BoardBuilder::new().x(0).build().self_symmetries(),
+            vec![Transform::FlipDiagonal]
+        );
+        assert!(
+            BoardBuilder::new()
+                .x(0)
+                .o(1)
+                .build()
+                .self_symmetries()
+                .is_empty()
+        );
+
+        // A center mark keeps every symmetry, a corner pair only some
+        assert_eq!(BoardBuilder::new().x(4).build().self_symmetries().len(), 7);
+        assert_eq!(
+            BoardBuilder::new().x(0).x(8).build().self_symmetries(),
+            vec![
+                Transform::Rotate180,
+                Transform::FlipDiagonal,
+                Transform::FlipAntiDiagonal
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_grid_round_trip() {
+        let boards = [
+            BoardBuilder::new().x(0).o(4).x(5).build(),
+            Board::with_size(4).unwrap(),
+            Board::with_size(1).unwrap(),
+        ];
+        for board in boards {
+            let grid = board.as_grid();
+            let rows: Vec<&[Cell]> = grid.iter().map(Vec::as_slice).collect();
+            assert_eq!(Board::from_grid(&rows), Ok(board));
+        }
+    }
+
+    #[test]
+    fn test_from_grid_errors() {
+        let e = Cell::Empty;
+        assert_eq!(Board::from_grid(&[]), Err(BoardError::ZeroSize));
+        assert_eq!(
+            Board::from_grid(&[&[e, e, e], &[e, e], &[e, e, e]]),
+            Err(BoardError::NotSquare { row: 1, len: 2 })
+        );
+        let row: &[Cell] = &[e; 7];
+        assert_eq!(
+            Board::from_grid(&[row; 7]),
+            Err(BoardError::TooLarge { size: 7, max: 6 })
+        );
+    }
+}
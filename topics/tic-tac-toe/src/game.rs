@@ -1,5 +1,11 @@
-use crate::board::Board;
+use crate::ai::{AI, Outcome};
+use crate::board::{self, Board, BoardBuilder, BoardError};
+use crate::rng::Rng;
+use crate::symmetry;
 use crate::types::{Cell, Player};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::Mutex;
 
 /// Represents the current state of the game
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -12,11 +18,287 @@ pub enum GameState {
     Draw,
 }
 
+impl GameState {
+    /// Returns the result of a finished game as an `Outcome`, e.g. to group
+    /// games on a dashboard; None while the game is in progress
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            GameState::InProgress => None,
+            GameState::Won(Player::Human) => Some(Outcome::HumanWins),
+            GameState::Won(Player::AI) => Some(Outcome::AiWins),
+            GameState::Draw => Some(Outcome::Draw),
+        }
+    }
+}
+
+/// Stage of a game, from how much of the board is filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Less than a third of the cells are filled
+    Opening,
+    /// Between a third and two thirds of the cells are filled
+    Midgame,
+    /// At least two thirds of the cells are filled
+    Endgame,
+}
+
+/// Summary of a game, typically computed once it has finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    /// Number of moves played
+    pub total_moves: usize,
+    /// The winner, if any
+    pub winner: Option<Player>,
+    /// The line that completed the win, if the game was won on the board
+    pub winning_line: Option<Vec<usize>>,
+    /// The first player to create a fork (two simultaneous threats), if any
+    pub fork_created_by: Option<Player>,
+}
+
+/// Marks held on one winning line, e.g. for an analysis heatmap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineStatus {
+    /// Positions of the line
+    pub cells: Vec<usize>,
+    /// Number of Human marks on the line
+    pub human: usize,
+    /// Number of AI marks on the line
+    pub ai: usize,
+}
+
+impl LineStatus {
+    /// Returns true if `player` could still complete the line, i.e. the
+    /// opponent has no mark on it
+    pub fn winnable_by(&self, player: Player) -> bool {
+        match player {
+            Player::Human => self.ai == 0,
+            Player::AI => self.human == 0,
+        }
+    }
+
+    /// Returns true if neither player can complete the line any more
+    pub fn is_blocked(&self) -> bool {
+        self.human > 0 && self.ai > 0
+    }
+}
+
+/// What a legal move would achieve, e.g. to annotate a move menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovePreview {
+    /// The cell played
+    pub position: usize,
+    /// The move completes a line for the player to move
+    pub wins_now: bool,
+    /// The move takes a cell where the opponent would win next turn
+    pub blocks_threat: bool,
+    /// The move leaves the player to move with two threats at once
+    pub creates_fork: bool,
+}
+
+/// A move the player to move has no real choice about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedMove {
+    /// The last empty cell
+    OnlyMove(usize),
+    /// The single cell stopping the opponent from winning next turn
+    ForcedBlock(usize),
+}
+
+impl ForcedMove {
+    /// Returns the cell to play
+    pub fn position(&self) -> usize {
+        match *self {
+            ForcedMove::OnlyMove(position) | ForcedMove::ForcedBlock(position) => position,
+        }
+    }
+}
+
+/// Reasons a move is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The game has already ended
+    GameOver,
+    /// The position is outside the board
+    OutOfBounds(usize),
+    /// The cell at this position is already taken
+    CellOccupied(usize),
+    /// The variant's `Restriction` forbids this position as the opening move
+    Restricted(usize),
+    /// The move was accepted but left the board unchanged
+    /// Never expected; reported so drivers stop instead of looping
+    NoProgress(usize),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::GameOver => write!(f, "the game is already over"),
+            MoveError::OutOfBounds(position) => write!(f, "position {position} is off the board"),
+            MoveError::CellOccupied(position) => write!(f, "position {position} is already taken"),
+            MoveError::Restricted(position) => {
+                write!(f, "position {position} is not allowed as the opening move")
+            }
+            MoveError::NoProgress(position) => {
+                write!(f, "playing position {position} did not change the board")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Reasons a move list is not a legal game, with the turn index (0-based)
+/// of the first offending move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameVerifyError {
+    /// The move is outside the board
+    OutOfBounds { turn: usize, position: usize },
+    /// The move is on a cell taken earlier in the game
+    CellOccupied { turn: usize, position: usize },
+    /// The move comes after the game was already won or drawn
+    MoveAfterEnd { turn: usize, position: usize },
+}
+
+impl fmt::Display for GameVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameVerifyError::OutOfBounds { turn, position } => {
+                write!(f, "move {turn}: position {position} is off the board")
+            }
+            GameVerifyError::CellOccupied { turn, position } => {
+                write!(f, "move {turn}: position {position} is already taken")
+            }
+            GameVerifyError::MoveAfterEnd { turn, position } => {
+                write!(
+                    f,
+                    "move {turn}: position {position} played after the game ended"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameVerifyError {}
+
+/// Reasons a board cannot be a reachable position with the given player to move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPosition {
+    /// One player has more than one extra mark compared to the other
+    MarkCountMismatch { human: usize, ai: usize },
+    /// The mark counts imply the other player should be moving
+    WrongPlayerToMove(Player),
+    /// Both players own a complete line, rejected by the default
+    /// `SimultaneousWinPolicy`
+    BothPlayersWon,
+}
+
+impl fmt::Display for InvalidPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidPosition::MarkCountMismatch { human, ai } => {
+                write!(f, "impossible mark counts: {human} X and {ai} O")
+            }
+            InvalidPosition::WrongPlayerToMove(player) => {
+                write!(
+                    f,
+                    "it cannot be {}'s turn in this position",
+                    player.symbol()
+                )
+            }
+            InvalidPosition::BothPlayersWon => write!(f, "both players have a winning line"),
+        }
+    }
+}
+
+impl std::error::Error for InvalidPosition {}
+
+/// How a position where both players own a complete line is scored
+///
+/// A single move never completes lines for both players, but imported
+/// positions or variant rules may show both with a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimultaneousWinPolicy {
+    /// Such positions are not reachable: `validate` rejects them
+    #[default]
+    RejectAsInvalid,
+    /// The player who moved last wins
+    MoverWins,
+    /// The game ends in a draw
+    Draw,
+}
+
+/// Variant rule forbidding some cells for the first move of a game, e.g.
+/// the center, to reduce the first player's advantage
+/// Later moves may use those cells freely
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Restriction {
+    banned_openings: Vec<usize>,
+}
+
+impl Restriction {
+    /// Forbids the given cells for the opening move
+    pub fn ban_openings(cells: &[usize]) -> Self {
+        Restriction {
+            banned_openings: cells.to_vec(),
+        }
+    }
+
+    /// Returns true if `position` may be played on `board`
+    /// Only an empty board is affected
+    pub fn allows(&self, board: &Board, position: usize) -> bool {
+        !board.is_empty() || !self.banned_openings.contains(&position)
+    }
+}
+
+/// Callback told about every move played on a game: position, then player
+pub type MoveObserver = Box<dyn FnMut(usize, Player) + Send>;
+
+/// Move observers registered on a game
+/// They are not copied by `clone` and ignored by comparisons, so searches
+/// on copies of a game never notify them
+#[derive(Default)]
+struct Observers(Vec<MoveObserver>);
+
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
+
+impl PartialEq for Observers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Observers {}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} observer(s)", self.0.len())
+    }
+}
+
 /// Represents the game logic and state
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
     board: Board,
     current_player: Player,
     state: GameState,
+    resigned: Option<Player>,
+    /// Positions played through `make_move`, in order
+    history: Vec<usize>,
+    /// Positions taken back by `undo` and not replayed yet, the most
+    /// recently undone last
+    redo: Vec<usize>,
+    /// Custom winning patterns used instead of the board's lines
+    patterns: Option<Vec<Vec<usize>>>,
+    /// How positions where both players own a line are scored
+    win_policy: SimultaneousWinPolicy,
+    /// Cells the variant forbids for the opening move
+    restriction: Restriction,
+    /// Callbacks notified after each move, in registration order
+    observers: Observers,
 }
 
 impl Game {
@@ -26,6 +308,22 @@ impl Game {
             board: Board::new(),
             current_player: Player::Human,
             state: GameState::InProgress,
+            resigned: None,
+            history: Vec::new(),
+            redo: Vec::new(),
+            patterns: None,
+            win_policy: SimultaneousWinPolicy::default(),
+            restriction: Restriction::default(),
+            observers: Observers::default(),
+        }
+    }
+
+    /// Creates a new game with `starter` moving first
+    /// Marks stay tied to players, so an AI start opens with an O
+    pub fn new_with_starter(starter: Player) -> Self {
+        Game {
+            current_player: starter,
+            ..Self::new()
         }
     }
 
@@ -35,11 +333,158 @@ impl Game {
             board,
             current_player,
             state: GameState::InProgress,
+            resigned: None,
+            history: Vec::new(),
+            redo: Vec::new(),
+            patterns: None,
+            win_policy: SimultaneousWinPolicy::default(),
+            restriction: Restriction::default(),
+            observers: Observers::default(),
+        };
+        game.update_state();
+        game
+    }
+
+    /// Creates a new game on an empty `size` x `size` board where
+    /// `win_length` marks in a row, column or diagonal win, e.g. 4 in a row
+    /// on 5x5
+    /// Fails for an unsupported size, or a win length of 0 or above `size`
+    pub fn with_win_length(size: usize, win_length: usize) -> Result<Self, BoardError> {
+        let board = Board::with_size(size)?;
+        if win_length == 0 || win_length > size {
+            return Err(BoardError::InvalidWinLength { win_length, size });
+        }
+        let lines = board::winning_lines(size, win_length);
+        Ok(Self::with_patterns(board, Player::Human, lines))
+    }
+
+    /// Creates a game from a board where a player wins by owning every cell
+    /// of one of `patterns`, instead of a row, column or diagonal
+    /// Empty patterns and patterns with positions off the board are ignored
+    pub fn with_patterns(board: Board, current_player: Player, patterns: Vec<Vec<usize>>) -> Self {
+        let cell_count = board.cells().len();
+        let patterns = patterns
+            .into_iter()
+            .filter(|pattern| !pattern.is_empty() && pattern.iter().all(|&idx| idx < cell_count))
+            .collect();
+
+        let mut game = Game {
+            board,
+            current_player,
+            state: GameState::InProgress,
+            resigned: None,
+            history: Vec::new(),
+            redo: Vec::new(),
+            patterns: Some(patterns),
+            win_policy: SimultaneousWinPolicy::default(),
+            restriction: Restriction::default(),
+            observers: Observers::default(),
+        };
+        game.update_state();
+        game
+    }
+
+    /// Creates a game on another board with the same winning patterns
+    pub(crate) fn with_board(&self, board: Board, current_player: Player) -> Self {
+        let mut game = Game {
+            board,
+            current_player,
+            state: GameState::InProgress,
+            resigned: None,
+            history: Vec::new(),
+            redo: Vec::new(),
+            patterns: self.patterns.clone(),
+            win_policy: self.win_policy,
+            restriction: self.restriction.clone(),
+            observers: Observers::default(),
         };
         game.update_state();
         game
     }
 
+    /// Returns the cell sets that win the game: the custom patterns if any,
+    /// otherwise the board's rows, columns and diagonals
+    fn lines(&self) -> &[Vec<usize>] {
+        self.patterns
+            .as_deref()
+            .unwrap_or_else(|| self.board.lines())
+    }
+
+    /// Creates a game from an existing board state, checking that it is
+    /// consistent with `current_player` moving next
+    /// See `validate` for the checks made
+    pub fn from_board_checked(
+        board: Board,
+        current_player: Player,
+    ) -> Result<Self, InvalidPosition> {
+        let game = Self::from_board(board, current_player);
+        game.validate()?;
+        Ok(game)
+    }
+
+    /// Checks that the position could arise in play with the current
+    /// player moving next
+    ///
+    /// Either player may have started, so equal mark counts accept both
+    /// players; otherwise the player with fewer marks must be to move.
+    /// Both players owning a line is an error unless the game's
+    /// `SimultaneousWinPolicy` says how to score it.
+    pub fn validate(&self) -> Result<(), InvalidPosition> {
+        let (human, ai) = self.board.counts();
+
+        if human.abs_diff(ai) > 1 {
+            return Err(InvalidPosition::MarkCountMismatch { human, ai });
+        }
+
+        let expected = match human.cmp(&ai) {
+            std::cmp::Ordering::Greater => Some(Player::AI),
+            std::cmp::Ordering::Less => Some(Player::Human),
+            std::cmp::Ordering::Equal => None,
+        };
+        if let Some(expected) = expected
+            && expected != self.current_player
+        {
+            return Err(InvalidPosition::WrongPlayerToMove(self.current_player));
+        }
+
+        if self.win_policy == SimultaneousWinPolicy::RejectAsInvalid
+            && self.check_winner(Player::Human)
+            && self.check_winner(Player::AI)
+        {
+            return Err(InvalidPosition::BothPlayersWon);
+        }
+
+        Ok(())
+    }
+
+    /// Sets how positions where both players own a line are scored, and
+    /// rescores the current position with it
+    pub fn with_win_policy(mut self, policy: SimultaneousWinPolicy) -> Self {
+        self.win_policy = policy;
+        if self.resigned.is_none() {
+            self.state = GameState::InProgress;
+            self.update_state();
+        }
+        self
+    }
+
+    /// Applies a variant restriction on the opening move, enforced by
+    /// `make_move` and respected by `available_moves`
+    pub fn with_restriction(mut self, restriction: Restriction) -> Self {
+        self.restriction = restriction;
+        self
+    }
+
+    /// Returns the restriction on the opening move
+    pub fn restriction(&self) -> &Restriction {
+        &self.restriction
+    }
+
+    /// Returns how positions where both players own a line are scored
+    pub fn win_policy(&self) -> SimultaneousWinPolicy {
+        self.win_policy
+    }
+
     /// Returns a reference to the current board
     pub fn board(&self) -> &Board {
         &self.board
@@ -50,26 +495,156 @@ impl Game {
         self.current_player
     }
 
+    /// Returns the symbol of the player to move ('X' or 'O')
+    pub fn current_symbol(&self) -> char {
+        self.current_player.symbol()
+    }
+
+    /// Returns the name of the player to move ("Human" or "AI")
+    pub fn current_player_name(&self) -> &'static str {
+        self.current_player.name()
+    }
+
     /// Returns the current game state
     pub fn state(&self) -> GameState {
         self.state
     }
 
+    /// Returns the positions played so far, in order
+    /// Only moves made through this game are recorded, not the initial board
+    pub fn history(&self) -> &[usize] {
+        &self.history
+    }
+
+    /// Returns the stage of the game from the share of filled cells, so the
+    /// thresholds scale with the board size (on 3x3: 0-2 marks, 3-5, 6+)
+    pub fn phase(&self) -> Phase {
+        let cells = self.board.cells().len();
+        let filled = cells - self.board.empty_cells().len();
+
+        if filled * 3 < cells {
+            Phase::Opening
+        } else if filled * 3 < cells * 2 {
+            Phase::Midgame
+        } else {
+            Phase::Endgame
+        }
+    }
+
+    /// Returns the 1-based move number at which `position` was filled
+    /// Returns None for empty cells and for marks of the initial board
+    pub fn move_index_of(&self, position: usize) -> Option<usize> {
+        self.history
+            .iter()
+            .position(|&played| played == position)
+            .map(|index| index + 1)
+    }
+
+    /// Names the opening from the first move: "Corner opening",
+    /// "Edge opening" or "Center opening"
+    /// Returns None before any move has been made
+    pub fn opening_name(&self) -> Option<&'static str> {
+        let &first = self.history.first()?;
+
+        // Count how many of the row and column are on the border
+        let (row, col) = self.board.coordinates(first)?;
+        let last = self.board.size() - 1;
+        let on_border = |i: usize| i == 0 || i == last;
+
+        match (on_border(row), on_border(col)) {
+            (true, true) => Some("Corner opening"),
+            (true, false) | (false, true) => Some("Edge opening"),
+            (false, false) => Some("Center opening"),
+        }
+    }
+
+    /// Returns the symmetry-canonical version of the current board
+    /// Games that are rotations or reflections of each other share it
+    pub fn canonical_form(&self) -> Board {
+        symmetry::canonical_board(&self.board)
+    }
+
+    /// Returns true on the classic "two corners" trap of a 3x3 board: X holds
+    /// two opposite corners, O the center, and O is to move
+    /// Any corner loses to a fork there; only an edge holds the draw
+    pub fn is_two_corner_trap(&self) -> bool {
+        if self.board.size() != 3
+            || self.state != GameState::InProgress
+            || self.current_player != Player::AI
+        {
+            return false;
+        }
+
+        let trap = symmetry::canonical_board(&BoardBuilder::new().x(0).x(8).o(4).build());
+        let canonical = self.canonical_form();
+        [Player::Human, Player::AI]
+            .into_iter()
+            .all(|player| canonical.occupancy(player) == trap.occupancy(player))
+    }
+
+    /// Returns the player who resigned, if the game ended by resignation
+    pub fn resigned_by(&self) -> Option<Player> {
+        self.resigned
+    }
+
+    /// Ends the game with the given player giving up
+    /// The opponent is declared the winner; has no effect if the game is already over
+    pub fn resign(&mut self, player: Player) {
+        if self.state != GameState::InProgress {
+            return;
+        }
+
+        self.state = GameState::Won(player.opponent());
+        self.resigned = Some(player);
+        log_event!(info, "{} resigned: {:?}", player.symbol(), self.state);
+    }
+
+    /// Registers a callback invoked after each successful move
+    /// Callbacks fire in registration order; copies of the game don't keep them
+    pub fn on_move(&mut self, callback: MoveObserver) {
+        self.observers.0.push(callback);
+    }
+
     /// Makes a move at the given position for the current player
     /// Returns true if the move was successful, false otherwise
     pub fn make_move(&mut self, position: usize) -> bool {
+        let mover = self.current_player;
+        if !self.apply_move(position) {
+            return false;
+        }
+
+        log_event!(debug, "{} played position {}", mover.symbol(), position);
+        if self.state != GameState::InProgress {
+            log_event!(info, "game over: {:?}", self.state);
+        }
+
+        for callback in &mut self.observers.0 {
+            callback(position, mover);
+        }
+
+        true
+    }
+
+    /// Plays `position` for the current player without logging it or
+    /// notifying observers, for moves that are only explored
+    pub(crate) fn apply_move(&mut self, position: usize) -> bool {
         // Check if game is already over
         if self.state != GameState::InProgress {
             return false;
         }
 
         // Try to make the move
-        if !self.board.make_move(position, self.current_player) {
+        if !self.restriction.allows(&self.board, position)
+            || !self.board.make_move(position, self.current_player)
+        {
             return false;
         }
 
-        // Update game state
-        self.update_state();
+        self.history.push(position);
+        self.redo.clear();
+
+        // Update game state, only looking at lines through the new mark
+        self.update_state_after(position);
 
         // Switch player if game is still in progress
         if self.state == GameState::InProgress {
@@ -79,92 +654,907 @@ impl Game {
         true
     }
 
+    /// Takes back the last move in the history, giving the turn back to
+    /// the player who made it
+    /// A finished game (resignation included) is back in progress; the move
+    /// can be replayed with `redo`
+    /// Returns false if no move was made through this game
+    pub fn undo(&mut self) -> bool {
+        let Some(position) = self.history.pop() else {
+            return false;
+        };
+        self.redo.push(position);
+        let Some(Cell::Occupied(mover)) = self.board.get(position) else {
+            unreachable!("played positions hold a mark");
+        };
+
+        self.board.undo_move(position);
+        self.current_player = mover;
+        self.resigned = None;
+        self.state = GameState::InProgress;
+        self.update_state();
+
+        log_event!(debug, "{} took back position {}", mover.symbol(), position);
+        true
+    }
+
+    /// Replays the move most recently taken back by `undo`
+    /// Returns false if there is nothing to redo: no move was undone, or a
+    /// new move was made since
+    pub fn redo(&mut self) -> bool {
+        let Some(position) = self.redo.pop() else {
+            return false;
+        };
+
+        // make_move starts a new line of play, dropping the moves to redo
+        let pending = std::mem::take(&mut self.redo);
+        let replayed = self.make_move(position);
+        self.redo = pending;
+        replayed
+    }
+
+    /// Returns a copy of the game with `position` played by the current
+    /// player, leaving this game untouched
+    /// Returns None if the move is not legal; observers are not notified
+    pub fn peek(&self, position: usize) -> Option<Game> {
+        let mut next = self.clone();
+        next.apply_move(position).then_some(next)
+    }
+
     /// Updates the game state by checking for wins or draws
     fn update_state(&mut self) {
-        // Check if current player won
-        if self.check_winner(self.current_player) {
-            self.state = GameState::Won(self.current_player);
+        // The player to move is the opponent of the one who moved last
+        let last_mover = self.current_player.opponent();
+        if let Some(state) = self.simultaneous_win(last_mover) {
+            self.state = state;
             return;
         }
 
+        // A finished game keeps its winner to move, as `make_move` leaves
+        // it, but a board set up with `from_board` may be won by either side
+        for player in [self.current_player, last_mover] {
+            if self.check_winner(player) {
+                self.state = GameState::Won(player);
+                return;
+            }
+        }
+
         // Check for draw (board is full and no winner)
         if self.board.is_full() {
             self.state = GameState::Draw;
         }
     }
 
-    /// Checks if the given player has won the game
-    pub fn check_winner(&self, player: Player) -> bool {
-        let cells = self.board.cells();
-        let target = Cell::Occupied(player);
-
-        // Check rows
-        for row in 0..3 {
-            if cells[row * 3] == target
-                && cells[row * 3 + 1] == target
-                && cells[row * 3 + 2] == target
-            {
-                return true;
-            }
+    /// Makes a move for the current player and returns the resulting state
+    /// Combines `make_move` and `state`, explaining why a move is rejected
+    pub fn play(&mut self, position: usize) -> Result<GameState, MoveError> {
+        if self.state != GameState::InProgress {
+            return Err(MoveError::GameOver);
         }
 
-        // Check columns
-        for col in 0..3 {
-            if cells[col] == target && cells[col + 3] == target && cells[col + 6] == target {
-                return true;
+        match self.board.get(position) {
+            None => Err(MoveError::OutOfBounds(position)),
+            Some(Cell::Occupied(_)) => Err(MoveError::CellOccupied(position)),
+            Some(Cell::Empty) if !self.restriction.allows(&self.board, position) => {
+                Err(MoveError::Restricted(position))
+            }
+            Some(Cell::Empty) => {
+                // A successful move always changes the position
+                let before = self.board.position_id();
+                if !self.make_move(position) || self.board.position_id() == before {
+                    return Err(MoveError::NoProgress(position));
+                }
+                Ok(self.state)
             }
         }
+    }
 
-        // Check diagonals
-        // Top-left to bottom-right
-        if cells[0] == target && cells[4] == target && cells[8] == target {
-            return true;
+    /// Updates the game state after the current player played `position`
+    /// Only the lines passing through `position` can have been completed
+    fn update_state_after(&mut self, position: usize) {
+        // Play stops at the first line, so the opponent cannot own one too
+        if self.completes_line(position, self.current_player) {
+            self.state = GameState::Won(self.current_player);
+            return;
         }
 
-        // Top-right to bottom-left
-        if cells[2] == target && cells[4] == target && cells[6] == target {
-            return true;
+        if self.board.is_full() {
+            self.state = GameState::Draw;
         }
+    }
+
+    /// Scores a position where both players own a line according to the
+    /// win policy, `mover` being the player who moved last
+    /// Returns None if at most one player owns a line, or if the policy
+    /// leaves such positions to the usual rules
+    fn simultaneous_win(&self, mover: Player) -> Option<GameState> {
+        let state = match self.win_policy {
+            SimultaneousWinPolicy::RejectAsInvalid => return None,
+            SimultaneousWinPolicy::MoverWins => GameState::Won(mover),
+            SimultaneousWinPolicy::Draw => GameState::Draw,
+        };
 
-        false
+        (self.check_winner(Player::Human) && self.check_winner(Player::AI)).then_some(state)
     }
 
-    /// Returns a list of available moves
-    pub fn available_moves(&self) -> Vec<usize> {
-        self.board.available_moves()
+    /// Returns true if `player` owns a whole line passing through `position`
+    fn completes_line(&self, position: usize, player: Player) -> bool {
+        let cells = self.board.cells();
+        let target = Cell::Occupied(player);
+
+        self.lines()
+            .iter()
+            .filter(|line| line.contains(&position))
+            .any(|line| line.iter().all(|&idx| cells[idx] == target))
     }
 
-    /// Evaluates the current board state for the minimax algorithm
-    /// Returns: +10 for AI win, -10 for Human win, 0 for draw or in progress
-    pub fn evaluate(&self) -> i32 {
-        if self.check_winner(Player::AI) {
-            10
-        } else if self.check_winner(Player::Human) {
-            -10
-        } else {
-            0
-        }
+    /// Checks if the given player has won the game
+    pub fn check_winner(&self, player: Player) -> bool {
+        let cells = self.board.cells();
+        let target = Cell::Occupied(player);
+
+        // Check rows, columns and both diagonals, or the custom patterns
+        self.lines()
+            .iter()
+            .any(|line| line.iter().all(|&idx| cells[idx] == target))
     }
-}
 
-impl Default for Game {
-    fn default() -> Self {
-        Self::new()
+    /// Returns the line that won the game, if it was won on the board
+    /// Lines are scanned rows first, then columns, then diagonals, or the
+    /// custom patterns in the order they were given
+    pub fn winning_line(&self) -> Option<Vec<usize>> {
+        match self.state {
+            GameState::Won(player) if self.resigned.is_none() => {
+                let target = Cell::Occupied(player);
+                let cells = self.board.cells();
+                self.lines()
+                    .iter()
+                    .find(|line| line.iter().all(|&idx| cells[idx] == target))
+                    .cloned()
+            }
+            _ => None,
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Returns the empty cells where `player` would complete a line
+    pub fn threats(&self, player: Player) -> Vec<usize> {
+        threats_on(&self.board, self.lines(), player)
+    }
 
-    #[test]
-    fn test_new_game() {
-        let game = Game::new();
-        assert_eq!(game.state(), GameState::InProgress);
-        assert_eq!(game.current_player(), Player::Human);
+    /// Returns the empty cells where the player to move would win at once
+    /// Empty once the game is over
+    pub fn winning_moves(&self) -> Vec<usize> {
+        if self.state != GameState::InProgress {
+            return Vec::new();
+        }
+        self.threats(self.current_player)
     }
 
-    #[test]
+    /// Returns the empty cells where the opponent of the player to move
+    /// would win on their next turn, i.e. the threats to defend against
+    /// Empty once the game is over
+    pub fn opponent_winning_moves(&self) -> Vec<usize> {
+        if self.state != GameState::InProgress {
+            return Vec::new();
+        }
+        self.threats(self.current_player.opponent())
+    }
+
+    /// Returns the cells that matter most right now: the player to move's
+    /// winning moves and the opponent's threats, in increasing order
+    /// A cell that is both is listed once; empty once the game is over
+    pub fn critical_cells(&self) -> Vec<usize> {
+        let mut cells = self.winning_moves();
+        cells.extend(self.opponent_winning_moves());
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    /// Returns every empty cell that would complete a line, paired with the
+    /// player it wins for, ordered by position
+    /// A cell winning for both players is listed for each, Human first;
+    /// empty once the game is over
+    pub fn all_critical_positions(&self) -> Vec<(usize, Player)> {
+        if self.state != GameState::InProgress {
+            return Vec::new();
+        }
+
+        let mut cells: Vec<(usize, Player)> = [Player::Human, Player::AI]
+            .into_iter()
+            .flat_map(|player| {
+                self.threats(player)
+                    .into_iter()
+                    .map(move |position| (position, player))
+            })
+            .collect();
+        cells.sort_by_key(|&(position, player)| (position, player == Player::AI));
+        cells
+    }
+
+    /// Reports the marks on every winning line, in the order of the board's
+    /// lines (or of the custom patterns)
+    pub fn line_status(&self) -> Vec<LineStatus> {
+        let cells = self.board.cells();
+        let count = |line: &[usize], player| {
+            line.iter()
+                .filter(|&&idx| cells[idx] == Cell::Occupied(player))
+                .count()
+        };
+
+        self.lines()
+            .iter()
+            .map(|line| LineStatus {
+                cells: line.clone(),
+                human: count(line, Player::Human),
+                ai: count(line, Player::AI),
+            })
+            .collect()
+    }
+
+    /// Describes every legal move for the player to move, in position order
+    /// Empty once the game is over
+    pub fn move_previews(&self) -> Vec<MovePreview> {
+        if self.state != GameState::InProgress {
+            return Vec::new();
+        }
+
+        let wins = self.winning_moves();
+        let blocks = self.opponent_winning_moves();
+        self.available_moves()
+            .into_iter()
+            .map(|position| MovePreview {
+                position,
+                wins_now: wins.contains(&position),
+                blocks_threat: blocks.contains(&position),
+                creates_fork: self.creates_fork(position, self.current_player),
+            })
+            .collect()
+    }
+
+    /// Returns true while the AI still has a line it could complete, i.e. a
+    /// line holding no Human mark
+    /// Once the game is over, true only if the AI won
+    pub fn ai_can_still_win(&self) -> bool {
+        match self.state {
+            GameState::Won(player) => player == Player::AI,
+            GameState::Draw => false,
+            GameState::InProgress => {
+                let cells = self.board.cells();
+                let blocker = Cell::Occupied(Player::Human);
+                self.lines()
+                    .iter()
+                    .any(|line| line.iter().all(|&idx| cells[idx] != blocker))
+            }
+        }
+    }
+
+    /// Returns the cell the current player must take to stop the opponent
+    /// from winning on their next move
+    /// Returns None if there is no threat, or if the opponent has a fork
+    /// (two threats), since no single block saves the game then
+    pub fn urgent_defense(&self) -> Option<usize> {
+        match self.opponent_winning_moves()[..] {
+            [position] => Some(position),
+            _ => None,
+        }
+    }
+
+    /// Returns the move the player to move is forced to play, if any: the
+    /// last empty cell, or the only block against an immediate loss
+    /// Nothing is forced while the player to move can win at once, nor
+    /// against a fork; None once the game is over
+    pub fn is_forced(&self) -> Option<ForcedMove> {
+        if self.state != GameState::InProgress {
+            return None;
+        }
+        if let [position] = self.board.available_moves_slice() {
+            return Some(ForcedMove::OnlyMove(*position));
+        }
+        if !self.winning_moves().is_empty() {
+            return None;
+        }
+        self.urgent_defense().map(ForcedMove::ForcedBlock)
+    }
+
+    /// Returns true if `player` placing a mark at `position` would leave
+    /// them with two or more threats at once (a fork)
+    pub fn creates_fork(&self, position: usize, player: Player) -> bool {
+        let mut board = self.board.clone();
+        board.make_move(position, player) && threats_on(&board, self.lines(), player).len() >= 2
+    }
+
+    /// Returns every empty cell where `player` would create a fork
+    pub fn fork_moves(&self, player: Player) -> Vec<usize> {
+        self.available_moves()
+            .into_iter()
+            .filter(|&position| self.creates_fork(position, player))
+            .collect()
+    }
+
+    /// Returns true if the player to move can keep the initiative: some
+    /// move gives them a new threat the opponent will have to answer
+    /// Lighter than looking for a forced win; false once the game is over
+    pub fn has_tempo(&self) -> bool {
+        if self.state != GameState::InProgress {
+            return false;
+        }
+
+        let player = self.current_player;
+        let existing = self.threats(player);
+        self.available_moves().into_iter().any(|position| {
+            let mut board = self.board.clone();
+            board.make_move(position, player);
+            threats_on(&board, self.lines(), player)
+                .iter()
+                .any(|threat| !existing.contains(threat))
+        })
+    }
+
+    /// Summarizes the game: move count, winner, winning line and first fork
+    pub fn stats(&self) -> GameStats {
+        let winner = match self.state {
+            GameState::Won(player) => Some(player),
+            _ => None,
+        };
+
+        GameStats {
+            total_moves: self.history.len(),
+            winner,
+            winning_line: self.winning_line(),
+            fork_created_by: self.first_fork(),
+        }
+    }
+
+    /// Replays the history to find the first player who created a fork
+    fn first_fork(&self) -> Option<Player> {
+        // Rewind to the board the history started from
+        let mut board = self.board.clone();
+        for &position in &self.history {
+            board.undo_move(position);
+        }
+
+        for &position in &self.history {
+            let Some(Cell::Occupied(player)) = self.board.get(position) else {
+                continue;
+            };
+            board.make_move(position, player);
+            if threats_on(&board, self.lines(), player).len() >= 2 {
+                return Some(player);
+            }
+        }
+
+        None
+    }
+
+    /// Replays the game, pairing each move with the AI's score for it just
+    /// before it was played
+    /// Scores are from the mover's point of view, as in `AI::evaluate_moves`,
+    /// so a blunder scores lower than the best alternative at that point
+    pub fn annotated_moves(&self, ai: &AI) -> Vec<(usize, i32)> {
+        self.scored_history(ai)
+            .into_iter()
+            .map(|(position, score, _)| (position, score))
+            .collect()
+    }
+
+    /// Returns the turn indices (0-based) of the moves that scored strictly
+    /// lower than the best move available at that point, for either side
+    /// Scores are the AI's, so a slower win than possible also counts
+    pub fn blunders(&self, ai: &AI) -> Vec<usize> {
+        self.scored_history(ai)
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, (_, score, best))| score < best)
+            .map(|(turn, _)| turn)
+            .collect()
+    }
+
+    /// Lets `ai` play both sides optimally until the game ends, and returns
+    /// the final state
+    /// Each side plays its first best move, as in `AI::best_line`
+    pub fn play_out(&mut self, ai: &AI) -> GameState {
+        for position in ai.best_line(self) {
+            self.make_move(position);
+        }
+        self.state
+    }
+
+    /// Plays the first `plies` of `moves` on a new game, X first, and
+    /// returns the game at that point; a longer `plies` plays every move
+    /// Moves past the prefix are not checked; an illegal move within it is
+    /// reported by its index (0-based)
+    pub fn replay_prefix(moves: &[usize], plies: usize) -> Result<Game, usize> {
+        let mut game = Game::new();
+        for (idx, &position) in moves.iter().take(plies).enumerate() {
+            if !game.make_move(position) {
+                return Err(idx);
+            }
+        }
+        Ok(game)
+    }
+
+    /// Checks that `moves` is a legal game from the empty 3x3 board, with X
+    /// (the Human side) moving first and the players alternating
+    /// Every move must be on the board and on an empty cell, and none may
+    /// follow a win or a draw; the first violation is reported
+    pub fn verify_history(moves: &[usize]) -> Result<(), GameVerifyError> {
+        let mut game = Game::new();
+
+        for (turn, &position) in moves.iter().enumerate() {
+            match game.play(position) {
+                Ok(_) => {}
+                Err(MoveError::GameOver) => {
+                    return Err(GameVerifyError::MoveAfterEnd { turn, position });
+                }
+                Err(MoveError::OutOfBounds(_)) => {
+                    return Err(GameVerifyError::OutOfBounds { turn, position });
+                }
+                // A plain game has no restriction, so Restricted never occurs
+                Err(
+                    MoveError::CellOccupied(_)
+                    | MoveError::NoProgress(_)
+                    | MoveError::Restricted(_),
+                ) => {
+                    return Err(GameVerifyError::CellOccupied { turn, position });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the turn index (0-based) at which the move histories of the
+    /// two games first differ
+    /// None if they are identical or one is a prefix of the other
+    pub fn first_divergence(&self, other: &Game) -> Option<usize> {
+        self.history
+            .iter()
+            .zip(&other.history)
+            .position(|(mine, theirs)| mine != theirs)
+    }
+
+    /// Replays the game, returning each move with its score and the best
+    /// score available at that point, both from the mover's point of view
+    fn scored_history(&self, ai: &AI) -> Vec<(usize, i32, i32)> {
+        let mut game = self.starting_position();
+        let mut scored = Vec::with_capacity(self.history.len());
+
+        for &position in &self.history {
+            let scores = ai.evaluate_moves(&game);
+            let score = scores
+                .iter()
+                .find(|&&(candidate, _)| candidate == position)
+                .map_or(0, |&(_, score)| score);
+            let best = scores
+                .iter()
+                .map(|&(_, score)| score)
+                .max()
+                .unwrap_or(score);
+            scored.push((position, score, best));
+            game.apply_move(position);
+        }
+
+        scored
+    }
+
+    /// Returns the game as it was before any move of the history was played
+    fn starting_position(&self) -> Game {
+        let mut board = self.board.clone();
+        for &position in &self.history {
+            board.undo_move(position);
+        }
+
+        let first_player = match self.history.first().and_then(|&p| self.board.get(p)) {
+            Some(Cell::Occupied(player)) => player,
+            _ => self.current_player,
+        };
+        self.with_board(board, first_player)
+    }
+
+    /// Returns a list of available moves
+    /// On the opening move, cells forbidden by the restriction are left out
+    pub fn available_moves(&self) -> Vec<usize> {
+        let mut moves = self.board.available_moves();
+        if self.board.is_empty() {
+            moves.retain(|&position| self.restriction.allows(&self.board, position));
+        }
+        moves
+    }
+
+    /// Returns how many moves can still be played at most (the number of
+    /// empty cells), or 0 once the game is over
+    pub fn max_remaining_moves(&self) -> usize {
+        if self.state != GameState::InProgress {
+            return 0;
+        }
+        self.board.available_moves().len()
+    }
+
+    /// Returns how many plies it takes to finish the game if both sides
+    /// play optimally (the winner as fast as possible, the loser as slowly
+    /// as possible), or None if the game is already over
+    pub fn plies_to_terminal(&self) -> Option<usize> {
+        if self.state != GameState::InProgress {
+            return None;
+        }
+        Some(self.solve().1)
+    }
+
+    /// Returns how many plies the player to move has left before losing,
+    /// if `solve` finds the position lost for them
+    /// The opponent is assumed to win as fast as possible and the player
+    /// to resist as long as possible; None if the player can still draw or
+    /// win, or once the game is over
+    pub fn fastest_loss(&self) -> Option<usize> {
+        if self.state != GameState::InProgress {
+            return None;
+        }
+
+        match self.solve() {
+            (-1, plies) => Some(plies),
+            _ => None,
+        }
+    }
+
+    /// Returns true if perfect play from here ends in a draw, as found by
+    /// `AI::classify`, e.g. to tell players early
+    /// The game state is left untouched; true for a game already drawn
+    pub fn is_theoretical_draw(&self) -> bool {
+        match self.state {
+            GameState::Draw => true,
+            GameState::Won(_) => false,
+            GameState::InProgress => AI::classify(self) == Outcome::Draw,
+        }
+    }
+
+    /// Returns true if the result is already settled whatever is played
+    /// next: the game is over, a player has a forced win, or no
+    /// line can be completed by either side any more
+    /// A position that merely draws under perfect play is not settled, as
+    /// either side can still win on a mistake
+    pub fn is_effectively_decided(&self) -> bool {
+        if self.state != GameState::InProgress {
+            return true;
+        }
+        AI::classify(self) != Outcome::Draw || self.line_status().iter().all(LineStatus::is_blocked)
+    }
+
+    /// Solves the position by exhaustive search, the exact solver behind
+    /// the perfect-play queries of this module and of `AI`
+    /// Returns the outcome for the player to move (1 win, 0 draw, -1 loss)
+    /// and the number of plies until the game ends under optimal play;
+    /// (0, 0) once the game is over
+    pub(crate) fn solve(&self) -> (i32, usize) {
+        let (value, plies, _) = self.solve_with(&mut HashMap::new());
+        (value, plies)
+    }
+
+    /// Returns the moves both sides play from here under optimal play, as
+    /// scored by `solve`: the winner wins as fast as possible and the loser
+    /// resists as long as possible, the first move winning ties
+    /// Empty once the game is over
+    pub(crate) fn solved_line(&self) -> Vec<usize> {
+        // Every position on the line belongs to the same game tree, so the
+        // solved positions are shared along it
+        let mut solved = HashMap::new();
+        let mut game = self.clone();
+        let mut line = Vec::new();
+
+        while let (_, _, Some(position)) = game.solve_with(&mut solved) {
+            game.apply_move(position);
+            line.push(position);
+        }
+        line
+    }
+
+    /// `solve`, also returning the best move, with solved positions cached
+    /// by position id
+    fn solve_with(
+        &self,
+        solved: &mut HashMap<u64, (i32, usize, Option<usize>)>,
+    ) -> (i32, usize, Option<usize>) {
+        if self.state != GameState::InProgress {
+            return (0, 0, None);
+        }
+        let id = self.board.position_id();
+        if let Some(&result) = solved.get(&id) {
+            return result;
+        }
+
+        let mut best: Option<(i32, usize, usize)> = None;
+        for position in self.available_moves() {
+            let mut next = self.clone();
+            next.apply_move(position);
+
+            let (value, plies) = match next.state {
+                GameState::Won(_) => (1, 1),
+                GameState::Draw => (0, 1),
+                GameState::InProgress => {
+                    let (value, plies, _) = next.solve_with(solved);
+                    (-value, plies + 1)
+                }
+            };
+
+            // Prefer better outcomes, then faster wins and slower losses
+            let better = match best {
+                None => true,
+                Some((best_value, best_plies, _)) => {
+                    value > best_value
+                        || (value == best_value && value > 0 && plies < best_plies)
+                        || (value == best_value && value < 0 && plies > best_plies)
+                }
+            };
+            if better {
+                best = Some((value, plies, position));
+            }
+        }
+
+        let result = best.map_or((0, 0, None), |(value, plies, position)| {
+            (value, plies, Some(position))
+        });
+        solved.insert(id, result);
+        result
+    }
+
+    /// Returns the candidates that are currently legal moves, in the given order
+    /// Occupied, restricted and out-of-range positions are filtered out
+    pub fn legal_moves_among(&self, candidates: &[usize]) -> Vec<usize> {
+        if self.state != GameState::InProgress {
+            return Vec::new();
+        }
+
+        // Build the set of empty cells once as a bitmask for O(1) lookups
+        let empty = self
+            .available_moves()
+            .into_iter()
+            .fold(0u64, |mask, position| mask | (1 << position));
+
+        let cell_count = self.board.cells().len();
+        candidates
+            .iter()
+            .copied()
+            .filter(|&position| position < cell_count && empty & (1 << position) != 0)
+            .collect()
+    }
+
+    /// Evaluates the current board state for the minimax algorithm
+    /// Returns: +10 for AI win, -10 for Human win, 0 for draw or in progress
+    pub fn evaluate(&self) -> i32 {
+        if self.check_winner(Player::AI) {
+            10
+        } else if self.check_winner(Player::Human) {
+            -10
+        } else {
+            0
+        }
+    }
+
+    /// Static estimate of the board from the AI's point of view
+    /// Every line holding marks of only one player counts for that player:
+    /// 1 for one mark, 10 for two, 100 for three, and so on
+    pub fn heuristic(&self) -> i32 {
+        let cells = self.board.cells();
+        self.lines()
+            .iter()
+            .map(|line| {
+                let count = |player| {
+                    line.iter()
+                        .filter(|&&idx| cells[idx] == Cell::Occupied(player))
+                        .count() as u32
+                };
+                match (count(Player::AI), count(Player::Human)) {
+                    (0, 0) => 0,
+                    (ai, 0) => 10i32.pow(ai - 1),
+                    (0, human) => -10i32.pow(human - 1),
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+
+    /// Returns a continuous read of the position from the AI's point of view,
+    /// positive when the AI is favored, e.g. for an evaluation bar
+    /// Finished games score 1000 for an AI win, -1000 for a Human win and
+    /// 0 for a draw; games in progress score their `heuristic`
+    pub fn balance(&self) -> i32 {
+        match self.state {
+            GameState::Won(Player::AI) => 1000,
+            GameState::Won(Player::Human) => -1000,
+            GameState::Draw => 0,
+            GameState::InProgress => self.heuristic(),
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the empty cells where `player` would complete one of `lines`
+/// on `board`
+fn threats_on(board: &Board, lines: &[Vec<usize>], player: Player) -> Vec<usize> {
+    let cells = board.cells();
+    let target = Cell::Occupied(player);
+    let mut threats: Vec<usize> = lines
+        .iter()
+        .filter_map(|line| {
+            let owned = line.iter().filter(|&&idx| cells[idx] == target).count();
+            let empty = line.iter().find(|&&idx| cells[idx].is_empty());
+            if owned + 1 == line.len() {
+                empty.copied()
+            } else {
+                None
+            }
+        })
+        .collect();
+    threats.sort_unstable();
+    threats.dedup();
+    threats
+}
+
+/// Plays random legal moves from a new game until it ends
+/// The same seed always produces the same game
+pub fn random_game(seed: u64) -> Game {
+    let mut rng = Rng::new(seed);
+    let mut game = Game::new();
+
+    while let Some(position) = rng.choose(&game.available_moves()) {
+        if !game.make_move(position) {
+            break;
+        }
+    }
+
+    game
+}
+
+/// Plays out every legal game from the empty board
+/// Returns the number of complete games as (human wins, AI wins, draws)
+pub fn enumerate_outcomes() -> (u32, u32, u32) {
+    let mut counts = (0, 0, 0);
+    count_outcomes(&Game::new(), &mut counts);
+    counts
+}
+
+/// Counts the distinct board positions reachable by legal play from the
+/// empty board (including the empty board itself)
+/// Play stops at finished games, and positions are deduplicated by id
+pub fn count_unique_positions() -> usize {
+    let mut seen = HashSet::new();
+    collect_positions(&Game::new(), &mut seen);
+    seen.len()
+}
+
+/// Tells whether the first player can force a win on an empty
+/// `board_size` x `board_size` board where `win_length` marks in a row win
+/// Returns Some(true) if the first player wins, Some(false) if the second
+/// player does, and None if perfect play ends in a draw (as on 3x3)
+///
+/// Every rule set is solved once by exhaustive search, then remembered.
+/// Only the smallest boards are tractable.
+///
+/// # Panics
+/// Panics if `board_size` is 0 or above `board::MAX_SIZE`
+pub fn is_first_player_win(board_size: usize, win_length: usize) -> Option<bool> {
+    static SOLVED: Mutex<BTreeMap<(usize, usize), Option<bool>>> = Mutex::new(BTreeMap::new());
+
+    let rules = (board_size, win_length);
+    if let Some(&result) = SOLVED.lock().unwrap().get(&rules) {
+        return result;
+    }
+
+    let board = Board::with_size(board_size).expect("unsupported board size");
+    let patterns = board::winning_lines(board_size, win_length);
+    let game = Game::with_patterns(board, Player::Human, patterns);
+    let result = match game.solve().0 {
+        1 => Some(true),
+        -1 => Some(false),
+        _ => None,
+    };
+
+    SOLVED.lock().unwrap().insert(rules, result);
+    result
+}
+
+/// Recursively records the position id of `game` and all its continuations
+fn collect_positions(game: &Game, seen: &mut HashSet<u64>) {
+    if !seen.insert(game.board().position_id()) {
+        return;
+    }
+
+    if game.state() == GameState::InProgress {
+        for position in game.available_moves() {
+            let mut next = game.clone();
+            next.apply_move(position);
+            collect_positions(&next, seen);
+        }
+    }
+}
+
+/// Recursively walks every continuation of `game`, tallying finished games
+fn count_outcomes(game: &Game, counts: &mut (u32, u32, u32)) {
+    match game.state() {
+        GameState::Won(Player::Human) => counts.0 += 1,
+        GameState::Won(Player::AI) => counts.1 += 1,
+        GameState::Draw => counts.2 += 1,
+        GameState::InProgress => {
+            for position in game.available_moves() {
+                let mut next = game.clone();
+                next.apply_move(position);
+                count_outcomes(&next, counts);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use crate::symmetry::Transform;
+
+    #[test]
+    fn test_new_game() {
+        let game = Game::new();
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.current_player(), Player::Human);
+    }
+
+    #[test]
+    fn test_is_effectively_decided() {
+        // X completes the top row next move
+        let game = Game::from_moves_str("1 4 2 5").unwrap();
+        assert!(game.is_effectively_decided());
+
+        // Drawn with best play, but both sides still have open lines
+        let game = Game::from_moves_str("5").unwrap();
+        assert!(!game.is_effectively_decided());
+
+        // Every line holds both marks: a dead draw with a cell left
+        let game = Game::from_moves_str("1 2 3 5 4 7 8 9").unwrap();
+        assert_eq!(game.state(), GameState::InProgress);
+        assert!(game.is_effectively_decided());
+    }
+
+    #[test]
+    fn test_has_tempo() {
+        // X needs two marks in a line to threaten anything
+        assert!(!Game::new().has_tempo());
+        assert!(!Game::from_moves_str("5").unwrap().has_tempo());
+
+        // X in the center can line up with any free cell
+        let game = Game::from_moves_str("5 1").unwrap();
+        assert!(game.has_tempo());
+
+        let won = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert!(!won.has_tempo());
+    }
+
+    #[test]
+    fn test_new_with_starter() {
+        let mut game = Game::new_with_starter(Player::AI);
+        assert_eq!(game.current_player(), Player::AI);
+        assert!(game.board().is_empty());
+
+        assert!(game.make_move(4));
+        assert_eq!(game.board().get(4), Some(Cell::Occupied(Player::AI)));
+        assert_eq!(game.current_player(), Player::Human);
+    }
+
+    #[test]
+    fn test_current_symbol_and_name() {
+        let mut game = Game::new();
+        assert_eq!(game.current_symbol(), 'X');
+        assert_eq!(game.current_player_name(), "Human");
+
+        game.make_move(4);
+        assert_eq!(game.current_symbol(), 'O');
+        assert_eq!(game.current_player_name(), "AI");
+    }
+
+    #[test]
     fn test_horizontal_win() {
         let mut game = Game::new();
         // Human wins with top row
@@ -175,6 +1565,7 @@ mod tests {
         game.make_move(2); // Human X - wins!
 
         assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![0, 1, 2]));
     }
 
     #[test]
@@ -189,6 +1580,7 @@ mod tests {
         game.make_move(6); // AI O - wins!
 
         assert_eq!(game.state(), GameState::Won(Player::AI));
+        assert_eq!(game.winning_line(), Some(vec![0, 3, 6]));
     }
 
     #[test]
@@ -202,29 +1594,1373 @@ mod tests {
         game.make_move(8); // Human X - wins!
 
         assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![0, 4, 8]));
     }
 
     #[test]
-    fn test_draw() {
-        let mut game = Game::new();
-        // Create a draw scenario
-        game.make_move(0); // Human X
-        game.make_move(1); // AI O
-        game.make_move(2); // Human X
-        game.make_move(4); // AI O
-        game.make_move(3); // Human X
-        game.make_move(5); // AI O
-        game.make_move(7); // Human X
-        game.make_move(6); // AI O
-        game.make_move(8); // Human X
+    fn test_winning_line_scan_order() {
+        // X's last move completes the top row and the left column at once
+        let game = Game::from_moves_str("2 5 3 6 4 8 7 9 1").unwrap();
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![0, 1, 2]));
 
-        assert_eq!(game.state(), GameState::Draw);
+        assert_eq!(Game::from_moves_str("5 1").unwrap().winning_line(), None);
+        let drawn = Game::from_moves_str("1 5 9 2 8 7 3 6 4").unwrap();
+        assert_eq!(drawn.state(), GameState::Draw);
+        assert_eq!(drawn.winning_line(), None);
+
+        // A resignation wins without a line
+        let mut resigned = Game::from_moves_str("5 1").unwrap();
+        resigned.resign(Player::Human);
+        assert_eq!(resigned.winning_line(), None);
     }
 
     #[test]
-    fn test_invalid_move() {
-        let mut game = Game::new();
-        game.make_move(0); // Human X
-        assert!(!game.make_move(0)); // Try to play same position
+    fn test_win_on_larger_board() {
+        let board = Board::with_size(4).unwrap();
+        let mut game = Game::from_board(board, Player::Human);
+
+        // Human fills the second column, AI plays on the first
+        for (human, ai) in [(1, 0), (5, 4), (9, 8)] {
+            game.make_move(human);
+            game.make_move(ai);
+        }
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.threats(Player::Human), vec![13]);
+
+        game.make_move(13);
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![1, 5, 9, 13]));
+    }
+
+    #[test]
+    fn test_four_in_a_row_on_5x5() {
+        let mut game = Game::with_win_length(5, 4).unwrap();
+        assert_eq!(game.board().size(), 5);
+
+        // X builds a diagonal from 6 while O plays along the top row
+        for (human, ai) in [(6, 0), (12, 1), (18, 2)] {
+            game.make_move(human);
+            game.make_move(ai);
+        }
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.threats(Player::Human), vec![24]);
+
+        game.make_move(24);
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![6, 12, 18, 24]));
+    }
+
+    #[test]
+    fn test_with_win_length_errors() {
+        assert_eq!(
+            Game::with_win_length(3, 4).unwrap_err(),
+            BoardError::InvalidWinLength {
+                win_length: 4,
+                size: 3
+            }
+        );
+        assert!(Game::with_win_length(3, 0).is_err());
+        assert_eq!(
+            Game::with_win_length(0, 3).unwrap_err(),
+            BoardError::ZeroSize
+        );
+        // The classic rules
+        let game = Game::with_win_length(3, 3).unwrap();
+        assert_eq!(game.lines().len(), 8);
+    }
+
+    #[test]
+    fn test_custom_patterns_four_corners() {
+        let corners = vec![vec![0, 2, 6, 8]];
+
+        // A full top row is not a win with only the corners pattern
+        let mut board = Board::new();
+        for position in [0, 1, 2] {
+            board.make_move(position, Player::Human);
+        }
+        let game = Game::with_patterns(board, Player::Human, corners.clone());
+        assert!(!game.check_winner(Player::Human));
+        assert_eq!(game.state(), GameState::InProgress);
+
+        let mut game = Game::with_patterns(Board::new(), Player::Human, corners);
+        for position in [0, 1, 2, 3, 6] {
+            game.make_move(position);
+        }
+        assert_eq!(game.threats(Player::Human), vec![8]);
+        // AI takes 4: the middle column is not a win either
+        game.make_move(4);
+        assert_eq!(game.state(), GameState::InProgress);
+
+        game.make_move(8); // Human X
+        assert!(game.check_winner(Player::Human));
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.winning_line(), Some(vec![0, 2, 6, 8]));
+    }
+
+    #[test]
+    fn test_custom_patterns_ai_plays_by_them() {
+        // Human holds three corners; only the last corner blocks the pattern
+        let mut board = Board::new();
+        for position in [0, 2, 6] {
+            board.make_move(position, Player::Human);
+        }
+        for position in [1, 4] {
+            board.make_move(position, Player::AI);
+        }
+        let game = Game::with_patterns(board, Player::AI, vec![vec![0, 2, 6, 8]]);
+        assert_eq!(AI::new().find_best_move(&game), Some(8));
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut game = Game::new();
+        // Create a draw scenario
+        game.make_move(0); // Human X
+        game.make_move(1); // AI O
+        game.make_move(2); // Human X
+        game.make_move(4); // AI O
+        game.make_move(3); // Human X
+        game.make_move(5); // AI O
+        game.make_move(7); // Human X
+        game.make_move(6); // AI O
+        game.make_move(8); // Human X
+
+        assert_eq!(game.state(), GameState::Draw);
+    }
+
+    #[test]
+    fn test_invalid_move() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        assert!(!game.make_move(0)); // Try to play same position
+    }
+
+    #[test]
+    fn test_play_returns_state() {
+        let mut game = Game::new();
+        assert_eq!(game.play(0), Ok(GameState::InProgress)); // Human X
+        assert_eq!(game.play(3), Ok(GameState::InProgress)); // AI O
+        assert_eq!(game.play(1), Ok(GameState::InProgress)); // Human X
+        assert_eq!(game.play(4), Ok(GameState::InProgress)); // AI O
+        assert_eq!(game.play(2), Ok(GameState::Won(Player::Human))); // Human X - wins!
+        assert_eq!(game.play(5), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn test_play_rejects_invalid_moves() {
+        let mut game = Game::new();
+        game.play(4).unwrap();
+
+        assert_eq!(game.play(4), Err(MoveError::CellOccupied(4)));
+        assert_eq!(game.play(9), Err(MoveError::OutOfBounds(9)));
+        // Rejected moves don't change whose turn it is
+        assert_eq!(game.current_player(), Player::AI);
+    }
+
+    #[test]
+    fn test_play_occupied_cell_never_advances() {
+        let mut game = Game::new();
+        game.play(4).unwrap();
+        let before = game.clone();
+
+        for _ in 0..3 {
+            assert_eq!(game.play(4), Err(MoveError::CellOccupied(4)));
+        }
+        assert_eq!(game, before);
+        assert_eq!(game.board().position_id(), before.board().position_id());
+
+        // Every accepted move changes the position
+        let id = game.board().position_id();
+        assert_eq!(game.play(0), Ok(GameState::InProgress));
+        assert_ne!(game.board().position_id(), id);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut game = Game::new();
+        assert!(!game.undo());
+
+        game.make_move(4);
+        game.make_move(0);
+        assert!(game.undo());
+        assert_eq!(game.history(), &[4]);
+        assert_eq!(game.board().get(0), Some(Cell::Empty));
+        assert_eq!(game.current_player(), Player::AI);
+
+        // Undoing the winning move reopens the game
+        let mut won = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert_eq!(won.state(), GameState::Won(Player::Human));
+        assert!(won.undo());
+        assert_eq!(won.state(), GameState::InProgress);
+        assert_eq!(won.current_player(), Player::Human);
+    }
+
+    #[test]
+    fn test_undo_after_win_and_redo() {
+        let mut game = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert!(!game.redo());
+
+        assert!(game.undo());
+        assert!(game.undo());
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.current_player(), Player::AI);
+        assert_eq!(game.history(), &[0, 3, 1]);
+
+        assert!(game.redo());
+        assert!(game.redo());
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+        assert_eq!(game.history(), &[0, 3, 1, 4, 2]);
+        assert!(!game.redo());
+    }
+
+    #[test]
+    fn test_new_move_clears_redo() {
+        let mut game = Game::from_moves_str("5 1 9").unwrap();
+        assert!(game.undo());
+        assert!(game.undo());
+
+        // O answers differently: the undone moves can't be replayed
+        assert!(game.make_move(2));
+        assert!(!game.redo());
+        assert_eq!(game.history(), &[4, 2]);
+        assert_eq!(game.board().get(8), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn test_resign() {
+        let mut game = Game::new();
+        game.make_move(4); // Human X
+        game.make_move(0); // AI O
+
+        // Human gives up mid-game
+        game.resign(Player::Human);
+
+        assert_eq!(game.state(), GameState::Won(Player::AI));
+        assert_eq!(game.resigned_by(), Some(Player::Human));
+
+        // No further moves are accepted
+        assert!(!game.make_move(8));
+        assert_eq!(game.board().get(8), Some(Cell::Empty));
+    }
+
+    #[test]
+    fn test_from_board_checked_consistent() {
+        // X has one extra mark, so O is to move
+        let board = BoardBuilder::new().x(0).o(4).x(8).build();
+        let game = Game::from_board_checked(board, Player::AI).unwrap();
+        assert_eq!(game.current_player(), Player::AI);
+
+        // Equal counts: either player may have started
+        let board = BoardBuilder::new().x(0).o(4).build();
+        assert!(Game::from_board_checked(board.clone(), Player::Human).is_ok());
+        assert!(Game::from_board_checked(board, Player::AI).is_ok());
+    }
+
+    #[test]
+    fn test_from_board_checked_inconsistent() {
+        // X has one extra mark, so it cannot be X's turn
+        let board = BoardBuilder::new().x(0).o(4).x(8).build();
+        assert_eq!(
+            Game::from_board_checked(board, Player::Human).unwrap_err(),
+            InvalidPosition::WrongPlayerToMove(Player::Human)
+        );
+
+        // X has two extra marks
+        let board = BoardBuilder::new().x(0).x(8).build();
+        assert_eq!(
+            Game::from_board_checked(board, Player::AI).unwrap_err(),
+            InvalidPosition::MarkCountMismatch { human: 2, ai: 0 }
+        );
+    }
+
+    #[test]
+    fn test_legal_moves_among() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+
+        // Occupied (0, 4), empty (1, 8) and out-of-range (9, 42) candidates
+        assert_eq!(game.legal_moves_among(&[0, 1, 4, 9, 8, 42]), vec![1, 8]);
+        assert!(game.legal_moves_among(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_legal_moves_among_finished_game() {
+        let mut game = Game::new();
+        game.resign(Player::Human);
+        assert!(game.legal_moves_among(&[0, 1, 2]).is_empty());
+    }
+
+    #[test]
+    fn test_opening_name() {
+        assert_eq!(Game::new().opening_name(), None);
+
+        let opening = |position| {
+            let mut game = Game::new();
+            game.make_move(position);
+            game.make_move(if position == 4 { 0 } else { 4 });
+            game.opening_name()
+        };
+
+        assert_eq!(opening(4), Some("Center opening"));
+        for corner in [0, 2, 6, 8] {
+            assert_eq!(opening(corner), Some("Corner opening"));
+        }
+        for edge in [1, 3, 5, 7] {
+            assert_eq!(opening(edge), Some("Edge opening"));
+        }
+    }
+
+    #[test]
+    fn test_canonical_form_of_symmetric_games() {
+        let play = |moves: &[usize]| {
+            let mut game = Game::new();
+            for &position in moves {
+                assert!(game.make_move(position));
+            }
+            game
+        };
+
+        // Human wins the top row
+        let moves = [0, 4, 1, 8, 2];
+        let game = play(&moves);
+        let rotated: Vec<usize> = moves
+            .iter()
+            .map(|&position| Transform::Rotate90.apply_index(position, 3))
+            .collect();
+        let rotated = play(&rotated);
+        // Human wins the left column instead, with the AI off the diagonal
+        let different = play(&[0, 5, 3, 8, 6]);
+
+        assert_ne!(game.board(), rotated.board());
+        assert_eq!(game.canonical_form(), rotated.canonical_form());
+        assert_ne!(game.canonical_form(), different.canonical_form());
+    }
+
+    #[test]
+    fn test_annotated_moves_expose_blunder() {
+        let mut game = Game::new();
+        game.make_move(4); // Human X
+        game.make_move(1); // AI O - edge reply to the center, a blunder
+        game.make_move(0); // Human X - threatens 8
+        game.make_move(8); // AI O
+        game.make_move(6); // Human X - fork on 2 and 3
+        game.make_move(3); // AI O
+        game.make_move(2); // Human X wins
+
+        let ai = AI::new();
+        let annotated = game.annotated_moves(&ai);
+        let positions: Vec<usize> = annotated.iter().map(|&(position, _)| position).collect();
+        assert_eq!(positions, game.history());
+
+        // A corner would have held the draw
+        let mut before_blunder = Game::new();
+        before_blunder.make_move(4);
+        let best = ai
+            .evaluate_moves(&before_blunder)
+            .iter()
+            .map(|&(_, score)| score)
+            .max();
+        assert_eq!(best, Some(0));
+        assert!(annotated[1].1 < 0);
+
+        // The winning side's moves all score as wins
+        assert_eq!(annotated[0].1, 0);
+        assert!(annotated[2].1 > 0 && annotated[4].1 > 0 && annotated[6].1 > 0);
+    }
+
+    #[test]
+    fn test_heuristic_counts_open_lines() {
+        assert_eq!(Game::new().heuristic(), 0);
+
+        let mut game = Game::new();
+        game.make_move(4); // Human X
+        // The center is on 4 lines, each with one X
+        assert_eq!(game.heuristic(), -4);
+
+        game.make_move(0); // AI O
+        // Lines through both marks count for nobody
+        assert_eq!(game.heuristic(), -3 + 2);
+    }
+
+    #[test]
+    fn test_balance_favors_ai_fork() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+        game.make_move(2); // AI O
+        game.make_move(8); // Human X
+        game.make_move(3); // AI O - threatens 5 and 6
+
+        assert!(game.balance() >= 10);
+    }
+
+    #[test]
+    fn test_balance_of_symmetric_positions() {
+        assert_eq!(Game::new().balance(), 0);
+
+        // Opposite corners mirror each other
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(8); // AI O
+        assert_eq!(game.balance(), 0);
+    }
+
+    #[test]
+    fn test_balance_of_finished_games() {
+        let mut game = Game::new();
+        game.resign(Player::Human);
+        assert_eq!(game.balance(), 1000);
+
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+        assert_eq!(game.balance(), -1000);
+    }
+
+    #[test]
+    fn test_blunders() {
+        let mut game = Game::new();
+        game.make_move(4); // Human X
+        game.make_move(1); // AI O - loses, a corner draws
+        game.make_move(0); // Human X
+        game.make_move(8); // AI O
+        game.make_move(6); // Human X - fork
+        game.make_move(3); // AI O - every move loses now
+        game.make_move(2); // Human X wins
+
+        assert_eq!(game.blunders(&AI::new()), vec![1]);
+    }
+
+    #[test]
+    fn test_no_blunders_in_perfect_game() {
+        let ai = AI::new();
+        let mut game = Game::new();
+        while let Some(position) = ai.all_best_moves(&game).first().copied() {
+            game.make_move(position);
+        }
+
+        assert_eq!(game.state(), GameState::Draw);
+        assert!(game.blunders(&ai).is_empty());
+    }
+
+    #[test]
+    fn test_max_remaining_moves() {
+        let mut game = Game::new();
+        assert_eq!(game.max_remaining_moves(), 9);
+        game.make_move(4);
+        assert_eq!(game.max_remaining_moves(), 8);
+        game.resign(Player::AI);
+        assert_eq!(game.max_remaining_moves(), 0);
+    }
+
+    #[test]
+    fn test_plies_to_terminal_mate_in_one() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(1); // Human X
+        game.make_move(4); // AI O
+
+        // Human wins right away at 2
+        assert_eq!(game.plies_to_terminal(), Some(1));
+    }
+
+    #[test]
+    fn test_plies_to_terminal_delayed_loss() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(1); // AI O
+        game.make_move(6); // Human X
+        game.make_move(3); // AI O
+        game.make_move(8); // Human X - fork
+
+        // AI blocks one threat, Human completes the other
+        assert_eq!(game.plies_to_terminal(), Some(2));
+    }
+
+    #[test]
+    fn test_plies_to_terminal_finished_game() {
+        let mut game = Game::new();
+        game.resign(Player::Human);
+        assert_eq!(game.plies_to_terminal(), None);
+    }
+
+    #[test]
+    fn test_ai_can_still_win_early() {
+        let mut game = Game::new();
+        assert!(game.ai_can_still_win());
+        game.make_move(4); // Human X
+        game.make_move(0); // AI O
+        assert!(game.ai_can_still_win());
+    }
+
+    #[test]
+    fn test_ai_can_still_win_all_lines_blocked() {
+        // Every line holds an X, but X can still win at 2 or 3
+        let board = BoardBuilder::new()
+            .x(0)
+            .x(4)
+            .x(5)
+            .x(6)
+            .o(1)
+            .o(7)
+            .o(8)
+            .build();
+        let game = Game::from_board(board, Player::AI);
+
+        assert!(!game.ai_can_still_win());
+        assert_eq!(game.threats(Player::Human), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_winning_moves() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(1); // Human X
+        // AI to move: only Human threatens, at 2
+        assert!(game.winning_moves().is_empty());
+
+        game.make_move(4); // AI O
+        assert_eq!(game.winning_moves(), vec![2]);
+
+        game.make_move(2); // Human X wins
+        assert!(game.winning_moves().is_empty());
+    }
+
+    #[test]
+    fn test_opponent_winning_moves() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X - threatens 2
+        assert_eq!(game.opponent_winning_moves(), vec![2]);
+        assert!(game.winning_moves().is_empty());
+
+        game.make_move(2); // AI O blocks, threatening 6
+        assert_eq!(game.opponent_winning_moves(), vec![6]);
+    }
+
+    #[test]
+    fn test_opponent_winning_moves_fork() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(8); // Human X
+        game.make_move(2); // AI O
+        game.make_move(6); // Human X - threatens 3 and 7
+        assert_eq!(game.opponent_winning_moves(), vec![3, 7]);
+        assert_eq!(game.urgent_defense(), None);
+    }
+
+    #[test]
+    fn test_urgent_defense_single_threat() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X - threatens 2
+
+        assert_eq!(game.urgent_defense(), Some(2));
+    }
+
+    #[test]
+    fn test_urgent_defense_fork() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(1); // AI O
+        game.make_move(6); // Human X
+        game.make_move(3); // AI O
+        game.make_move(8); // Human X - threatens 4 and 7
+
+        assert_eq!(game.urgent_defense(), None);
+    }
+
+    #[test]
+    fn test_urgent_defense_quiet_position() {
+        let mut game = Game::new();
+        assert_eq!(game.urgent_defense(), None);
+        game.make_move(4); // Human X
+        game.make_move(0); // AI O
+        assert_eq!(game.urgent_defense(), None);
+    }
+
+    #[test]
+    fn test_is_forced_last_cell() {
+        let mut game = Game::new();
+        for position in [0, 4, 8, 2, 6, 3, 5, 7] {
+            game.make_move(position);
+        }
+
+        // Cell 1 also blocks O's middle column, but it is the only move left
+        assert_eq!(game.is_forced(), Some(ForcedMove::OnlyMove(1)));
+        assert_eq!(game.is_forced().unwrap().position(), 1);
+        game.make_move(1);
+        assert_eq!(game.is_forced(), None);
+    }
+
+    #[test]
+    fn test_is_forced_block() {
+        let mut game = Game::new();
+        assert_eq!(game.is_forced(), None);
+
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X - threatens 2
+        assert_eq!(game.is_forced(), Some(ForcedMove::ForcedBlock(2)));
+
+        // With a win of its own available, X doesn't have to block
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        assert_eq!(game.opponent_winning_moves(), vec![5]);
+        assert_eq!(game.winning_moves(), vec![2]);
+        assert_eq!(game.is_forced(), None);
+    }
+
+    #[test]
+    fn test_is_forced_fork() {
+        let mut game = Game::new();
+        for position in [0, 1, 6, 3, 8] {
+            game.make_move(position);
+        }
+        // No single block stops both threats
+        assert_eq!(game.is_forced(), None);
+    }
+
+    #[test]
+    fn test_fastest_loss() {
+        let mut game = Game::new();
+        assert_eq!(game.fastest_loss(), None);
+
+        // X must block the left column, after which O forks on 8
+        for position in [1, 0, 2, 6] {
+            game.make_move(position);
+        }
+        assert_eq!(game.fastest_loss(), Some(4));
+
+        game.make_move(3); // Human X
+        game.make_move(8); // AI O - threatens 4 and 7
+        assert_eq!(game.fastest_loss(), Some(2));
+
+        // The winner has no loss coming, nor does anyone after the end
+        game.make_move(4); // Human X
+        assert_eq!(game.fastest_loss(), None);
+        game.make_move(7); // AI O
+        assert_eq!(game.fastest_loss(), None);
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_moves_are_logged() {
+        use std::sync::OnceLock;
+        use std::thread::{self, ThreadId};
+
+        /// Keeps the records logged from the thread that installed it, so
+        /// tests running in parallel don't interfere
+        struct Capture {
+            owner: OnceLock<ThreadId>,
+            records: Mutex<Vec<(log::Level, String)>>,
+        }
+
+        impl log::Log for Capture {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                self.owner.get() == Some(&thread::current().id())
+            }
+
+            fn log(&self, record: &log::Record) {
+                if self.enabled(record.metadata()) {
+                    let message = record.args().to_string();
+                    self.records.lock().unwrap().push((record.level(), message));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        static CAPTURE: Capture = Capture {
+            owner: OnceLock::new(),
+            records: Mutex::new(Vec::new()),
+        };
+        let explored = Game::from_moves_str("5 1").unwrap();
+        CAPTURE.owner.set(thread::current().id()).unwrap();
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        // Moves that are only explored leave no record
+        assert!(explored.peek(8).is_some());
+        AI::forced_win_in(&explored, 3);
+        assert_eq!(*CAPTURE.records.lock().unwrap(), []);
+
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+
+        let records = CAPTURE.records.lock().unwrap();
+        assert_eq!(records.len(), 6);
+        assert_eq!(
+            records[0],
+            (log::Level::Debug, "X played position 0".to_string())
+        );
+        assert_eq!(
+            records[5],
+            (log::Level::Info, "game over: Won(Human)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_two_corner_trap() {
+        let mut game = Game::new();
+        for position in [0, 4, 8] {
+            game.make_move(position);
+        }
+        assert!(game.is_two_corner_trap());
+        // Taking a corner walks into the fork
+        assert!(AI::new().is_losing_move(&game, 2));
+        assert!(!AI::new().is_losing_move(&game, 1));
+
+        // Recognized on the other diagonal too
+        let mut game = Game::new();
+        for position in [6, 4, 2] {
+            game.make_move(position);
+        }
+        assert!(game.is_two_corner_trap());
+
+        // Adjacent corners are a different position
+        let mut game = Game::new();
+        for position in [0, 4, 2] {
+            game.make_move(position);
+        }
+        assert!(!game.is_two_corner_trap());
+
+        // So is O in the corner with X in the center
+        let mut game = Game::new();
+        for position in [4, 0, 8] {
+            game.make_move(position);
+        }
+        assert!(!game.is_two_corner_trap());
+    }
+
+    #[test]
+    fn test_center_opening_banned() {
+        let mut game = Game::new().with_restriction(Restriction::ban_openings(&[4]));
+        assert_eq!(game.available_moves(), vec![0, 1, 2, 3, 5, 6, 7, 8]);
+        assert!(!game.make_move(4));
+        assert_eq!(game.play(4), Err(MoveError::Restricted(4)));
+        assert!(game.history().is_empty());
+
+        // Only the opening move is restricted
+        assert!(game.make_move(0));
+        assert!(game.available_moves().contains(&4));
+        assert!(game.make_move(4));
+        assert_eq!(game.board().get(4), Some(Cell::Occupied(Player::AI)));
+    }
+
+    #[test]
+    fn test_ai_opening_respects_restriction() {
+        let restriction = Restriction::ban_openings(&[4]);
+        let game = Game::from_board(Board::new(), Player::AI).with_restriction(restriction);
+
+        let position = AI::new().find_best_move(&game).unwrap();
+        assert_ne!(position, 4);
+        assert!(game.clone().make_move(position));
+        for seed in 0..20 {
+            let ai = AI::new().with_opening_variety(true).with_seed(seed);
+            assert_ne!(ai.find_best_move(&game), Some(4));
+        }
+        assert_eq!(crate::strategy::rule_based_move(&game), Some(0));
+    }
+
+    #[test]
+    fn test_all_critical_positions() {
+        // X threatens 3 on the top row, O threatens 6 on the middle row
+        let game = Game::from_moves_str("1 4 2 5").unwrap();
+        assert_eq!(
+            game.all_critical_positions(),
+            vec![(2, Player::Human), (5, Player::AI)]
+        );
+
+        // Cell 7 completes the left column for X and the diagonal for O
+        let game = Game::from_moves_str("1 5 4 3 2").unwrap();
+        assert_eq!(
+            game.all_critical_positions(),
+            vec![(6, Player::Human), (6, Player::AI)]
+        );
+
+        assert!(Game::new().all_critical_positions().is_empty());
+    }
+
+    #[test]
+    fn test_is_theoretical_draw() {
+        let game = Game::new();
+        assert!(game.is_theoretical_draw());
+        assert_eq!(game.state(), GameState::InProgress);
+
+        // O forks after X blocks the left column
+        let game = Game::from_moves_str("2 1 3 7").unwrap();
+        assert!(!game.is_theoretical_draw());
+
+        let game = Game::from_moves_str("5 1 9 3 2 8 4 6 7").unwrap();
+        assert_eq!(game.state(), GameState::Draw);
+        assert!(game.is_theoretical_draw());
+    }
+
+    #[test]
+    fn test_replay_prefix() {
+        let moves = [4, 0, 8, 2, 1];
+        let game = Game::replay_prefix(&moves, 2).unwrap();
+        assert_eq!(game.board(), &BoardBuilder::new().x(4).o(0).build());
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.current_player(), Player::Human);
+
+        // Going past the end plays every move
+        let game = Game::replay_prefix(&moves, 10).unwrap();
+        assert_eq!(game.history(), &moves);
+        assert_eq!(Game::replay_prefix(&moves, 0).unwrap(), Game::new());
+
+        // Only the replayed moves need to be legal
+        let moves = [4, 0, 4];
+        assert!(Game::replay_prefix(&moves, 2).is_ok());
+        assert_eq!(Game::replay_prefix(&moves, 3), Err(2));
+    }
+
+    #[test]
+    fn test_creates_fork() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(1); // AI O
+        game.make_move(6); // Human X
+        game.make_move(3); // AI O
+
+        assert!(game.creates_fork(8, Player::Human));
+        assert!(!game.creates_fork(2, Player::Human));
+        // Occupied cells never fork
+        assert!(!game.creates_fork(0, Player::Human));
+    }
+
+    #[test]
+    fn test_fork_moves_single_cell() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(8); // Human X
+        game.make_move(2); // AI O
+
+        // Only 6 gives X two threats (3 and 7)
+        assert_eq!(game.fork_moves(Player::Human), vec![6]);
+    }
+
+    #[test]
+    fn test_fork_moves_none() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+
+        // X can make a threat, but never two at once
+        assert!(game.fork_moves(Player::Human).is_empty());
+    }
+
+    #[test]
+    fn test_stats_after_fork_win() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(1); // AI O
+        game.make_move(6); // Human X - threatens 3
+        game.make_move(3); // AI O - blocks
+        game.make_move(8); // Human X - fork: threatens 7 and 4
+        game.make_move(7); // AI O - blocks one threat
+        game.make_move(4); // Human X - wins on the diagonal
+
+        let stats = game.stats();
+        assert_eq!(stats.total_moves, 7);
+        assert_eq!(stats.winner, Some(Player::Human));
+        assert_eq!(stats.winning_line, Some(vec![0, 4, 8]));
+        assert_eq!(stats.fork_created_by, Some(Player::Human));
+    }
+
+    #[test]
+    fn test_stats_after_resignation() {
+        let mut game = Game::new();
+        game.make_move(4); // Human X
+        game.resign(Player::AI);
+
+        let stats = game.stats();
+        assert_eq!(stats.total_moves, 1);
+        assert_eq!(stats.winner, Some(Player::Human));
+        assert_eq!(stats.winning_line, None);
+        assert_eq!(stats.fork_created_by, None);
+    }
+
+    #[test]
+    fn test_incremental_state_matches_full_scan() {
+        for seed in 0..200 {
+            let mut rng = Rng::new(seed);
+            let mut game = Game::new();
+
+            while game.state() == GameState::InProgress {
+                let position = rng.choose(&game.available_moves()).unwrap();
+                game.make_move(position);
+
+                let expected = if game.check_winner(Player::Human) {
+                    GameState::Won(Player::Human)
+                } else if game.check_winner(Player::AI) {
+                    GameState::Won(Player::AI)
+                } else if game.board().is_full() {
+                    GameState::Draw
+                } else {
+                    GameState::InProgress
+                };
+                assert_eq!(game.state(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_games_always_finish_validly() {
+        for seed in 0..1000 {
+            let game = random_game(seed);
+            let cells = game.board().cells();
+            let count = |player| {
+                cells
+                    .iter()
+                    .filter(|&&cell| cell == Cell::Occupied(player))
+                    .count()
+            };
+            let (human, ai) = (count(Player::Human), count(Player::AI));
+
+            // Human moves first, so X leads by at most one mark
+            assert!(human == ai || human == ai + 1);
+            assert_eq!(game.history().len(), human + ai);
+
+            match game.state() {
+                GameState::Won(player) => {
+                    assert!(game.check_winner(player));
+                    assert!(!game.check_winner(player.opponent()));
+                }
+                GameState::Draw => assert!(game.board().is_full()),
+                GameState::InProgress => panic!("random game {seed} did not finish"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_game_is_deterministic() {
+        assert_eq!(random_game(42), random_game(42));
+    }
+
+    #[test]
+    fn test_count_unique_positions() {
+        // Known number of legal tic-tac-toe positions, empty board included
+        assert_eq!(count_unique_positions(), 5478);
+    }
+
+    #[test]
+    fn test_enumerate_outcomes() {
+        // Known totals for 3x3 tic-tac-toe: 255,168 possible games,
+        // of which the first player (Human) wins 131,184 and the second 77,904
+        let (human_wins, ai_wins, draws) = enumerate_outcomes();
+
+        assert_eq!(human_wins, 131_184);
+        assert_eq!(ai_wins, 77_904);
+        assert_eq!(draws, 46_080);
+        assert_eq!(human_wins + ai_wins + draws, 255_168);
+    }
+
+    #[test]
+    fn test_is_first_player_win() {
+        // Standard tic-tac-toe is a draw
+        assert_eq!(is_first_player_win(3, 3), None);
+        // Two in a row: the first player forks at once
+        assert_eq!(is_first_player_win(3, 2), Some(true));
+        assert_eq!(is_first_player_win(2, 2), Some(true));
+        // Nobody can line up four marks on a 3x3 board
+        assert_eq!(is_first_player_win(3, 4), None);
+        // Memoized results are the same
+        assert_eq!(is_first_player_win(3, 3), None);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let play = |moves: &[usize]| {
+            let mut game = Game::new();
+            for &position in moves {
+                game.make_move(position);
+            }
+            game
+        };
+
+        let game = play(&[4, 0, 8, 2]);
+        assert_eq!(game.first_divergence(&play(&[4, 0, 8, 2])), None);
+        assert_eq!(game.first_divergence(&play(&[4, 0])), None);
+        assert_eq!(play(&[4, 0]).first_divergence(&game), None);
+        assert_eq!(game.first_divergence(&play(&[4, 0, 8, 6])), Some(3));
+        assert_eq!(game.first_divergence(&play(&[0])), Some(0));
+    }
+
+    #[test]
+    fn test_play_out() {
+        let ai = AI::new();
+        let mut game = Game::new();
+        assert_eq!(game.play_out(&ai), GameState::Draw);
+        assert!(game.board().is_full());
+
+        // X to move completes the bottom row
+        let mut game = Game::new();
+        for position in [0, 4, 8, 2, 6, 3] {
+            game.make_move(position);
+        }
+        assert_eq!(game.play_out(&ai), GameState::Won(Player::Human));
+        assert_eq!(game.history().len(), 7);
+
+        // Nothing changes once the game is over
+        assert_eq!(game.play_out(&ai), GameState::Won(Player::Human));
+        assert_eq!(game.history().len(), 7);
+    }
+
+    #[test]
+    fn test_verify_history_valid() {
+        assert_eq!(Game::verify_history(&[]), Ok(()));
+        assert_eq!(Game::verify_history(&[4, 0, 8, 2]), Ok(()));
+        // Human wins on the top row with the last move
+        assert_eq!(Game::verify_history(&[0, 3, 1, 4, 2]), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_history_errors() {
+        assert_eq!(
+            Game::verify_history(&[0, 3, 1, 4, 2, 5]),
+            Err(GameVerifyError::MoveAfterEnd {
+                turn: 5,
+                position: 5
+            })
+        );
+        assert_eq!(
+            Game::verify_history(&[4, 0, 4]),
+            Err(GameVerifyError::CellOccupied {
+                turn: 2,
+                position: 4
+            })
+        );
+        assert_eq!(
+            Game::verify_history(&[4, 9]),
+            Err(GameVerifyError::OutOfBounds {
+                turn: 1,
+                position: 9
+            })
+        );
+
+        // A full drawn board accepts no further move
+        let draw = [0, 1, 2, 4, 3, 5, 7, 6, 8];
+        assert_eq!(Game::verify_history(&draw), Ok(()));
+        let mut too_long = draw.to_vec();
+        too_long.push(0);
+        assert!(matches!(
+            Game::verify_history(&too_long),
+            Err(GameVerifyError::MoveAfterEnd { turn: 9, .. })
+        ));
+    }
+
+    #[test]
+    fn test_on_move_observers() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut game = Game::new();
+        for tag in ["first", "second"] {
+            let seen = Arc::clone(&seen);
+            game.on_move(Box::new(move |position, player| {
+                seen.lock().unwrap().push((tag, position, player));
+            }));
+        }
+
+        game.make_move(4);
+        // Rejected moves are not reported
+        game.make_move(4);
+        game.play(0).unwrap();
+        // Moves on a copy are not reported either
+        game.clone().make_move(8);
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                ("first", 4, Player::Human),
+                ("second", 4, Player::Human),
+                ("first", 0, Player::AI),
+                ("second", 0, Player::AI),
+            ]
+        );
+    }
+
+    /// X owns the top row and O the middle row
+    fn double_win_board() -> Board {
+        BoardBuilder::new().x(0).x(1).x(2).o(3).o(4).o(5).build()
+    }
+
+    /// Plays towards X's top row after O completes the middle row
+    /// Through moves a double line never arises: play stops at O's line
+    fn double_line_attempt(policy: SimultaneousWinPolicy) -> Result<GameState, MoveError> {
+        let mut game = Game::new().with_win_policy(policy);
+        for position in [0, 3, 1, 4, 8] {
+            assert_eq!(game.play(position), Ok(GameState::InProgress));
+        }
+        assert_eq!(game.play(5), Ok(GameState::Won(Player::AI)));
+        game.play(2)
+    }
+
+    #[test]
+    fn test_simultaneous_win_rejected_by_default() {
+        assert_eq!(
+            Game::from_board_checked(double_win_board(), Player::Human),
+            Err(InvalidPosition::BothPlayersWon)
+        );
+
+        let game = Game::from_board(double_win_board(), Player::Human);
+        assert_eq!(game.win_policy(), SimultaneousWinPolicy::RejectAsInvalid);
+        assert_eq!(game.validate(), Err(InvalidPosition::BothPlayersWon));
+    }
+
+    #[test]
+    fn test_simultaneous_win_mover_wins() {
+        // X is to move, so O moved last
+        let game = Game::from_board(double_win_board(), Player::Human)
+            .with_win_policy(SimultaneousWinPolicy::MoverWins);
+        assert_eq!(game.validate(), Ok(()));
+        assert_eq!(game.state(), GameState::Won(Player::AI));
+        assert_eq!(
+            double_line_attempt(SimultaneousWinPolicy::MoverWins),
+            Err(MoveError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_simultaneous_win_draw() {
+        let game = Game::from_board(double_win_board(), Player::Human)
+            .with_win_policy(SimultaneousWinPolicy::Draw);
+        assert_eq!(game.validate(), Ok(()));
+        assert_eq!(game.state(), GameState::Draw);
+        assert_eq!(
+            double_line_attempt(SimultaneousWinPolicy::Draw),
+            Err(MoveError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_simultaneous_win_import_without_line_to_move() {
+        // O owns a line with X to move: O moved last and has already won
+        let board = BoardBuilder::new().x(0).x(1).o(3).o(4).o(5).x(8).build();
+        for policy in [
+            SimultaneousWinPolicy::RejectAsInvalid,
+            SimultaneousWinPolicy::MoverWins,
+            SimultaneousWinPolicy::Draw,
+        ] {
+            let game = Game::from_board(board.clone(), Player::Human).with_win_policy(policy);
+            assert_eq!(game.state(), GameState::Won(Player::AI));
+        }
+    }
+
+    #[test]
+    fn test_move_index_of() {
+        let mut game = Game::new();
+        for position in [4, 0, 8, 2] {
+            game.make_move(position);
+        }
+
+        assert_eq!(game.move_index_of(4), Some(1));
+        assert_eq!(game.move_index_of(8), Some(3));
+        assert_eq!(game.move_index_of(2), Some(4));
+        assert_eq!(game.move_index_of(1), None);
+        assert_eq!(game.move_index_of(9), None);
+
+        // Marks of the starting board were not played in this game
+        let game = Game::from_board(BoardBuilder::new().x(4).build(), Player::AI);
+        assert_eq!(game.move_index_of(4), None);
+    }
+
+    #[test]
+    fn test_phase_3x3() {
+        let mut game = Game::new();
+        assert_eq!(game.phase(), Phase::Opening);
+
+        let phases: Vec<Phase> = [0, 1, 2, 4, 3, 5, 7, 6]
+            .into_iter()
+            .map(|position| {
+                game.make_move(position);
+                game.phase()
+            })
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                Phase::Opening,
+                Phase::Opening,
+                Phase::Midgame,
+                Phase::Midgame,
+                Phase::Midgame,
+                Phase::Endgame,
+                Phase::Endgame,
+                Phase::Endgame,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_phase_4x4() {
+        let board = Board::with_size(4).unwrap();
+        assert_eq!(
+            Game::from_board(board.clone(), Player::Human).phase(),
+            Phase::Opening
+        );
+
+        // Five marks are still an opening on 16 cells, fourteen an endgame
+        let mut game = Game::from_board(board, Player::Human);
+        for position in [0, 5, 1, 6, 2] {
+            game.make_move(position);
+        }
+        assert_eq!(game.phase(), Phase::Opening);
+        game.make_move(8);
+        assert_eq!(game.phase(), Phase::Midgame);
+
+        let mut board = Board::with_size(4).unwrap();
+        for position in 0..14 {
+            let player = if position % 2 == 0 {
+                Player::Human
+            } else {
+                Player::AI
+            };
+            board.make_move(position, player);
+        }
+        assert_eq!(
+            Game::from_board(board, Player::Human).phase(),
+            Phase::Endgame
+        );
+    }
+
+    #[test]
+    fn test_critical_cells() {
+        // X can win on 2, O threatens 5
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        assert_eq!(game.critical_cells(), vec![2, 5]);
+
+        // Both players complete a line on 2
+        let mut game = Game::new();
+        for position in [0, 5, 1, 8] {
+            game.make_move(position);
+        }
+        assert_eq!(game.critical_cells(), vec![2]);
+
+        assert!(Game::new().critical_cells().is_empty());
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut game = Game::new();
+        game.make_move(4);
+        let before = game.clone();
+
+        let next = game.peek(0).unwrap();
+        assert_eq!(next.history(), &[4, 0]);
+        assert_eq!(next.board().get(0), Some(Cell::Occupied(Player::AI)));
+        assert_eq!(next.current_player(), Player::Human);
+        assert_eq!(game, before);
+
+        assert!(game.peek(4).is_none());
+        assert!(game.peek(9).is_none());
+
+        // A winning move ends the peeked game only
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        assert_eq!(game.peek(2).unwrap().state(), GameState::Won(Player::Human));
+        assert_eq!(game.state(), GameState::InProgress);
+    }
+
+    #[test]
+    fn test_move_previews() {
+        // X to move: 2 wins the top row, 5 blocks O's middle row
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        let previews = game.move_previews();
+        assert_eq!(
+            previews
+                .iter()
+                .map(|preview| preview.position)
+                .collect::<Vec<_>>(),
+            game.available_moves()
+        );
+
+        let find = |position| *previews.iter().find(|p| p.position == position).unwrap();
+        assert_eq!(
+            find(2),
+            MovePreview {
+                position: 2,
+                wins_now: true,
+                blocks_threat: false,
+                creates_fork: false,
+            }
+        );
+        assert!(find(5).blocks_threat && !find(5).wins_now);
+        for position in [6, 7, 8] {
+            assert_eq!(
+                (find(position).wins_now, find(position).blocks_threat),
+                (false, false)
+            );
+        }
+
+        game.make_move(2);
+        assert!(game.move_previews().is_empty());
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(GameState::InProgress.outcome(), None);
+        assert_eq!(
+            GameState::Won(Player::Human).outcome(),
+            Some(Outcome::HumanWins)
+        );
+        assert_eq!(GameState::Won(Player::AI).outcome(), Some(Outcome::AiWins));
+        assert_eq!(GameState::Draw.outcome(), Some(Outcome::Draw));
+
+        // A finished game's outcome is the one `AI::classify` gives it
+        let game = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert_eq!(game.state().outcome(), Some(AI::classify(&game)));
+    }
+
+    #[test]
+    fn test_line_status() {
+        // X on 0 and 4, O on 2 and 5
+        let mut game = Game::new();
+        for position in [0, 2, 4, 5] {
+            game.make_move(position);
+        }
+        let status = game.line_status();
+        assert_eq!(status.len(), 8);
+        let line = |cells: &[usize]| status.iter().find(|s| s.cells == cells).unwrap();
+
+        // Top row holds marks of both players
+        let top = line(&[0, 1, 2]);
+        assert_eq!((top.human, top.ai), (1, 1));
+        assert!(top.is_blocked());
+        assert!(!top.winnable_by(Player::Human) && !top.winnable_by(Player::AI));
+
+        // Right column holds two O marks only
+        let right = line(&[2, 5, 8]);
+        assert_eq!((right.human, right.ai), (0, 2));
+        assert!(right.winnable_by(Player::AI));
+        assert!(!right.winnable_by(Player::Human));
+
+        // Bottom row is still open to both
+        let bottom = line(&[6, 7, 8]);
+        assert!(bottom.winnable_by(Player::Human) && bottom.winnable_by(Player::AI));
     }
 }
@@ -0,0 +1,170 @@
+//! Fixed-size boards whose dimensions are known at compile time.
+//!
+//! `GridBoard<N>` stores its cells inline, without any heap allocation,
+//! which suits hot loops that always play on the same board size. The
+//! runtime-sized `Board` remains the type used by `Game` and the AI.
+//!
+//! Stable Rust can't size an array as `N * N` from a const generic, so the
+//! cells are kept as `N` rows of `N` cells. Positions are still numbered
+//! row by row from 0, as on `Board`.
+
+use crate::types::{Cell, Player};
+
+/// An N x N board with inline storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridBoard<const N: usize> {
+    cells: [[Cell; N]; N],
+}
+
+impl<const N: usize> GridBoard<N> {
+    /// Creates a new empty board
+    pub fn new() -> Self {
+        GridBoard {
+            cells: [[Cell::Empty; N]; N],
+        }
+    }
+
+    /// Returns the number of rows (and columns) of the board
+    pub fn size(&self) -> usize {
+        N
+    }
+
+    /// Returns the cell at the given position
+    pub fn get(&self, position: usize) -> Option<Cell> {
+        (position < N * N).then(|| self.cells[position / N][position % N])
+    }
+
+    /// Places a player's mark at the given position
+    /// Returns true if the move was successful, false otherwise
+    pub fn make_move(&mut self, position: usize, player: Player) -> bool {
+        if self.get(position) != Some(Cell::Empty) {
+            return false;
+        }
+
+        self.cells[position / N][position % N] = Cell::Occupied(player);
+        true
+    }
+
+    /// Removes the mark at the given position
+    /// Returns true if a mark was removed, false if the cell was empty or invalid
+    pub fn undo_move(&mut self, position: usize) -> bool {
+        match self.get(position) {
+            Some(Cell::Occupied(_)) => {
+                self.cells[position / N][position % N] = Cell::Empty;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns a list of all available moves (empty cell positions)
+    pub fn available_moves(&self) -> Vec<usize> {
+        (0..N * N)
+            .filter(|&position| self.get(position) == Some(Cell::Empty))
+            .collect()
+    }
+
+    /// Returns true if the board is full (no available moves)
+    pub fn is_full(&self) -> bool {
+        self.cells.iter().flatten().all(|cell| !cell.is_empty())
+    }
+
+    /// Checks if the given player owns a whole row, column or diagonal
+    pub fn check_winner(&self, player: Player) -> bool {
+        if N == 0 {
+            return false;
+        }
+
+        let target = Cell::Occupied(player);
+        let owns = |row: usize, col: usize| self.cells[row][col] == target;
+
+        (0..N).any(|row| (0..N).all(|col| owns(row, col)))
+            || (0..N).any(|col| (0..N).all(|row| owns(row, col)))
+            || (0..N).all(|i| owns(i, i))
+            || (0..N).all(|i| owns(i, N - 1 - i))
+    }
+}
+
+impl<const N: usize> Default for GridBoard<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::game::Game;
+    use crate::rng::Rng;
+
+    /// Plays the same random marks on both boards, checking after each one
+    /// that they agree on the winner, then returns the number of wins seen
+    fn compare_with_board<const N: usize>(seed: u64) -> usize {
+        let mut rng = Rng::new(seed);
+        let mut grid = GridBoard::<N>::new();
+        let mut board = Board::with_size(N).unwrap();
+        let mut player = Player::Human;
+        let mut wins = 0;
+
+        while let Some(position) = rng.choose(&grid.available_moves()) {
+            assert!(grid.make_move(position, player));
+            assert!(board.make_move(position, player));
+            assert_eq!(grid.available_moves(), board.available_moves());
+
+            let game = Game::from_board(board.clone(), player);
+            for side in [Player::Human, Player::AI] {
+                assert_eq!(grid.check_winner(side), game.check_winner(side));
+            }
+            if grid.check_winner(player) {
+                wins += 1;
+                break;
+            }
+            player = player.opponent();
+        }
+
+        assert_eq!(grid.is_full(), board.is_full());
+        wins
+    }
+
+    #[test]
+    fn test_grid_matches_board_3x3() {
+        let wins: usize = (0..50).map(compare_with_board::<3>).sum();
+        assert!(wins > 0);
+    }
+
+    #[test]
+    fn test_grid_matches_board_4x4() {
+        let wins: usize = (0..50).map(compare_with_board::<4>).sum();
+        assert!(wins > 0);
+    }
+
+    #[test]
+    fn test_grid_4x4_win() {
+        let mut grid = GridBoard::<4>::new();
+        assert_eq!(grid.size(), 4);
+
+        // Anti-diagonal: 3, 6, 9, 12
+        for position in [3, 6, 9] {
+            grid.make_move(position, Player::AI);
+        }
+        assert!(!grid.check_winner(Player::AI));
+        assert!(grid.make_move(12, Player::AI));
+        assert!(grid.check_winner(Player::AI));
+        assert!(!grid.check_winner(Player::Human));
+    }
+
+    #[test]
+    fn test_grid_moves() {
+        let mut grid = GridBoard::<3>::default();
+        assert!(grid.make_move(4, Player::Human));
+        assert!(!grid.make_move(4, Player::AI));
+        assert!(!grid.make_move(9, Player::AI));
+        assert_eq!(grid.get(4), Some(Cell::Occupied(Player::Human)));
+        assert_eq!(grid.get(9), None);
+
+        assert!(grid.undo_move(4));
+        assert!(!grid.undo_move(4));
+        assert_eq!(grid, GridBoard::new());
+    }
+}
@@ -0,0 +1,33 @@
+//! Tic-Tac-Toe engine: board representation, game rules and a Minimax AI.
+//!
+//! The `tic-tac-toe` binary is a command-line front-end built on top of
+//! these modules.
+//!
+//! With the `logging` feature, moves, game results and AI searches are
+//! reported through the `log` crate, for applications embedding the engine
+//! to route to their own logger. The `serde` feature makes `ai::AiConfig`
+//! serializable, to store AI presets.
+
+/// Emits a `log` record at the given level when the `logging` feature is
+/// enabled, and compiles to nothing otherwise
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "logging")]
+        log::$level!($($arg)+);
+    };
+}
+
+pub mod ai;
+pub mod analysis;
+pub mod board;
+pub mod game;
+pub mod grid;
+pub mod net;
+pub mod notation;
+pub mod puzzle;
+pub mod rng;
+pub mod source;
+pub mod strategy;
+pub mod symmetry;
+pub mod tournament;
+pub mod types;
@@ -1,44 +1,152 @@
-mod ai;
-mod board;
-mod game;
-mod types;
-
-use ai::AI;
-use game::{Game, GameState};
-use std::io::{self, Write};
-use types::Player;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tic_tac_toe::ai::{AI, Difficulty, SearchStats};
+use tic_tac_toe::board::{Board, GlyphSet};
+use tic_tac_toe::game::{Game, GameState, InvalidPosition};
+use tic_tac_toe::rng::Rng;
+use tic_tac_toe::tournament::{Match, MatchConfig};
+use tic_tac_toe::types::{Cell, Player};
 
 fn main() {
+    // --verbose prints the AI's reasoning before each of its moves
+    let verbose = std::env::args().skip(1).any(|arg| arg == "--verbose");
+    // --move-time SECS makes the human lose on time if they don't move in SECS seconds
+    let move_time = parse_move_time(std::env::args().skip(1));
+    // --edit sets up a custom starting position before playing
+    let edit = std::env::args().skip(1).any(|arg| arg == "--edit");
+    // --blindfold hides the board and only announces the moves
+    let blindfold = std::env::args().skip(1).any(|arg| arg == "--blindfold");
+    // --best-of N plays a series, stopping as soon as it is clinched
+    let best_of = parse_best_of(std::env::args().skip(1));
+    // --random-start [SEED] lets a coin flip decide who moves first
+    let random_start = parse_random_start(std::env::args().skip(1));
+    // --difficulty LEVEL and --blunder P set how strong the AI plays
+    let ai = match parse_ai(std::env::args().skip(1)) {
+        Ok(ai) => ai,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
     println!("=================================");
     println!("   Welcome to Tic-Tac-Toe!");
     println!("=================================");
     println!();
     println!("You are X, AI is O");
-    println!("Enter positions 1-9 as shown:");
+    println!("Enter positions 1-9 as shown, or \"row,col\" / a cell like \"b2\"");
+    println!("(\"hint\" for a suggestion, \"undo\" to take back a move, \"resign\" to give up,");
+    println!("\"q\" to quit):");
     println!();
     display_position_guide();
     println!();
 
     let mut game = Game::new();
-    let ai = AI::new();
+    if edit {
+        match run_editor(&mut io::stdin().lock()) {
+            Some(edited) => game = edited,
+            None => {
+                println!();
+                println!("No more input, goodbye");
+                return;
+            }
+        }
+    } else if let Some(seed) = random_start {
+        game = coin_flip_game(seed, 0);
+    }
+    // Timed play reads stdin on a background thread so the prompt can give up
+    let timed_reader = move_time.map(|_| TimedReader::spawn(io::BufReader::new(io::stdin())));
+    let options = PlayOptions {
+        verbose,
+        blindfold,
+        move_time,
+    };
+    let mut series = best_of.map(|games| Match::new(MatchConfig::best_of(games)));
+    let mut game_index = 0;
+
+    while let Some(state) = run_game(game, &ai, timed_reader.as_ref(), options) {
+        let Some(series) = series.as_mut() else {
+            break;
+        };
+        series.record(state);
+        println!(
+            "Match score: you {} - {} AI",
+            series.points(Player::Human),
+            series.points(Player::AI)
+        );
+        if series.remaining_games() == 0 {
+            match series.winner() {
+                Some(Player::Human) => println!("You win the match!"),
+                Some(Player::AI) => println!("The AI wins the match!"),
+                None => println!("The match is tied!"),
+            }
+            break;
+        }
+        println!();
+        println!("Next game ({} left at most)", series.remaining_games());
+        game_index += 1;
+        game = match random_start {
+            Some(seed) => coin_flip_game(seed, game_index),
+            None => Game::new(),
+        };
+    }
+
+    println!();
+    println!("Thanks for playing!");
+}
+
+/// Display and clock settings shared by every game of a session
+#[derive(Debug, Clone, Copy)]
+struct PlayOptions {
+    /// Print the AI's reasoning and search statistics
+    verbose: bool,
+    /// Announce moves instead of drawing the board
+    blindfold: bool,
+    /// Time the human has for each move
+    move_time: Option<Duration>,
+}
+
+/// Plays `game` to the end at the terminal
+/// Returns the final state, or None if the player quit or input ran out
+fn run_game(
+    mut game: Game,
+    ai: &AI,
+    timed_reader: Option<&TimedReader>,
+    options: PlayOptions,
+) -> Option<GameState> {
+    let PlayOptions {
+        verbose,
+        blindfold,
+        move_time,
+    } = options;
+    let mut lost_on_time = false;
 
     loop {
-        // Display the current board
-        game.board().display();
+        // Display the current board, or only the last move when blindfolded
+        print!("{}", turn_display(&game, blindfold));
 
         // Check game state
         match game.state() {
             GameState::Won(Player::Human) => {
                 println!("Congratulations! You won!");
-                break;
+                return Some(game.state());
             }
             GameState::Won(Player::AI) => {
+                if lost_on_time {
+                    println!("Time's up! You lose on time.");
+                } else if game.resigned_by() == Some(Player::Human) {
+                    println!("You resigned.");
+                }
                 println!("AI wins! Better luck next time!");
-                break;
+                return Some(game.state());
             }
             GameState::Draw => {
                 println!("It's a draw! Well played!");
-                break;
+                return Some(game.state());
             }
             GameState::InProgress => {
                 // Game continues
@@ -49,7 +157,50 @@ fn main() {
         if game.current_player() == Player::Human {
             // Human turn
             println!("Your turn (X)");
-            let position = get_human_move(&game);
+            let input = match (timed_reader, move_time) {
+                (Some(reader), Some(limit)) => get_timed_human_move(&game, reader, limit),
+                _ => get_human_move(&game, &mut io::stdin().lock()),
+            };
+            let position = match input {
+                Input::Move(position) => position,
+                Input::Resign => {
+                    game.resign(Player::Human);
+                    continue;
+                }
+                Input::Undo => {
+                    if undo_pair(&mut game) {
+                        println!("Took back your last move and the AI's reply");
+                    } else {
+                        println!("Nothing to undo yet");
+                    }
+                    continue;
+                }
+                Input::Hint => {
+                    if let Some(suggestion) = ai.suggest(&game) {
+                        println!(
+                            "Hint: position {}, {}",
+                            game.board().to_display_index(suggestion.position),
+                            suggestion.reason
+                        );
+                    }
+                    continue;
+                }
+                Input::TimedOut => {
+                    println!();
+                    lost_on_time = true;
+                    game.resign(Player::Human);
+                    continue;
+                }
+                Input::Quit => {
+                    println!("Game abandoned");
+                    return None;
+                }
+                Input::Closed => {
+                    println!();
+                    println!("No more input, goodbye");
+                    return None;
+                }
+            };
 
             if !game.make_move(position) {
                 println!("Invalid move! Try again.");
@@ -58,49 +209,556 @@ fn main() {
         } else {
             // AI turn
             println!("AI is thinking...");
+            if verbose {
+                println!("{}", ai.explain(&game));
+            }
 
-            if let Some(position) = ai.find_best_move(&game) {
+            if let Some((position, elapsed)) = timed_best_move(ai, &game) {
                 game.make_move(position);
-                println!("AI played position {}", position + 1);
+                println!(
+                    "AI played position {} (thought for {})",
+                    game.board().to_display_index(position),
+                    format_duration(elapsed)
+                );
+                if verbose {
+                    let stats = SearchStats {
+                        elapsed: Some(elapsed),
+                        ..ai.last_search_stats()
+                    };
+                    println!("Search: {}", stats.report());
+                }
             } else {
                 println!("Error: AI couldn't find a move!");
-                break;
+                return None;
             }
         }
     }
+}
 
-    println!();
-    println!("Thanks for playing!");
+/// Takes back the human's last move and the AI's reply, so it is the
+/// human's turn again
+/// Returns false, leaving the game untouched, if fewer than two moves
+/// were played
+fn undo_pair(game: &mut Game) -> bool {
+    if game.history().len() < 2 {
+        return false;
+    }
+    game.undo() && game.undo()
+}
+
+/// Runs the AI search and measures how long it took
+fn timed_best_move(ai: &AI, game: &Game) -> Option<(usize, Duration)> {
+    let start = Instant::now();
+    let position = ai.find_best_move(game)?;
+    Some((position, start.elapsed()))
+}
+
+/// Formats a thinking time with a precision suited to its magnitude
+/// (3x3 searches usually take well under a millisecond)
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_millis(1) {
+        format!("{} µs", duration.as_micros())
+    } else if duration < Duration::from_secs(1) {
+        format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.2} s", duration.as_secs_f64())
+    }
 }
 
-/// Gets a valid move from the human player
-fn get_human_move(game: &Game) -> usize {
+/// What the human player asked for at the move prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    /// Play at the given position (0-8)
+    Move(usize),
+    /// Give up the game
+    Resign,
+    /// Take back the last move pair
+    Undo,
+    /// Ask the AI for a suggested move
+    Hint,
+    /// Leave immediately without finishing the game
+    Quit,
+    /// The input stream was closed (EOF)
+    Closed,
+    /// No valid move was entered before the deadline
+    TimedOut,
+}
+
+/// Gets a valid move from the human player, reading lines from `reader`
+/// Keeps prompting until a valid move, a command or the end of input
+fn get_human_move<R: BufRead>(game: &Game, reader: &mut R) -> Input {
     loop {
-        print!("Enter position (1-9): ");
-        io::stdout().flush().unwrap();
+        prompt();
 
         let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-
-        // Try to parse the input
-        match input.trim().parse::<usize>() {
-            Ok(num) if (1..=9).contains(&num) => {
-                let position = num - 1; // Convert to 0-indexed
-
-                // Check if position is available
-                if game.available_moves().contains(&position) {
-                    return position;
-                } else {
-                    println!("That position is already taken! Try another.");
+        let bytes_read = reader.read_line(&mut input).expect("Failed to read line");
+
+        // A zero-byte read means stdin was closed: stop instead of re-prompting forever
+        if bytes_read == 0 {
+            return Input::Closed;
+        }
+
+        if let Some(input) = check_input(&input, game) {
+            return input;
+        }
+    }
+}
+
+/// Same as `get_human_move`, but gives up with `Input::TimedOut` once `limit`
+/// has passed (invalid lines don't reset the clock)
+fn get_timed_human_move(game: &Game, reader: &TimedReader, limit: Duration) -> Input {
+    let deadline = Instant::now() + limit;
+    loop {
+        prompt();
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let input = match reader.read_line(remaining) {
+            TimedLine::Line(line) => line,
+            TimedLine::Closed => return Input::Closed,
+            TimedLine::TimedOut => return Input::TimedOut,
+        };
+
+        if let Some(input) = check_input(&input, game) {
+            return input;
+        }
+    }
+}
+
+/// Prints the move prompt
+fn prompt() {
+    print!("Enter position (1-9): ");
+    io::stdout().flush().unwrap();
+}
+
+/// Parses a line typed at the prompt and checks the move is available
+/// Returns None (after telling the player why) when they must try again
+fn check_input(line: &str, game: &Game) -> Option<Input> {
+    match parse_input(line, game.board()) {
+        Ok(Input::Move(position)) => {
+            // Check if position is available
+            if game.available_moves().contains(&position) {
+                Some(Input::Move(position))
+            } else {
+                println!("That position is already taken! Try another.");
+                None
+            }
+        }
+        Ok(command) => Some(command),
+        Err(error) => {
+            println!("{error}");
+            None
+        }
+    }
+}
+
+/// Result of waiting for a line from a `TimedReader`
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TimedLine {
+    /// A full line, including its newline
+    Line(String),
+    /// The input stream was closed (EOF or read error)
+    Closed,
+    /// No line arrived before the timeout
+    TimedOut,
+}
+
+/// Reads lines on a background thread so waiting for one can time out
+/// A line that arrives after a timeout is kept for the next read
+struct TimedReader {
+    lines: Receiver<String>,
+}
+
+impl TimedReader {
+    /// Starts reading `reader` line by line on a background thread
+    fn spawn<R: BufRead + Send + 'static>(mut reader: R) -> Self {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let mut line = String::new();
+                match reader.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
                 }
             }
-            _ => {
-                println!("Invalid input! Please enter a number between 1 and 9.");
+        });
+        TimedReader { lines }
+    }
+
+    /// Waits at most `timeout` for the next line
+    fn read_line(&self, timeout: Duration) -> TimedLine {
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => TimedLine::Line(line),
+            Err(RecvTimeoutError::Timeout) => TimedLine::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => TimedLine::Closed,
+        }
+    }
+}
+
+/// Finds the "--move-time SECS" option among the command-line arguments
+/// Returns None when it is missing or SECS is not a positive number
+fn parse_move_time(mut args: impl Iterator<Item = String>) -> Option<Duration> {
+    args.find(|arg| arg == "--move-time")?;
+    let seconds: f64 = args.next()?.parse().ok()?;
+    (seconds.is_finite() && seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+/// Reads the "--best-of N" command-line option: the number of games in
+/// the series, None if the option is absent or N is not a positive number
+fn parse_best_of(mut args: impl Iterator<Item = String>) -> Option<u32> {
+    args.find(|arg| arg == "--best-of")?;
+    args.next()?.parse().ok().filter(|&games| games > 0)
+}
+
+/// Reads the "--random-start [SEED]" command-line option
+/// Returns the coin flip seed, taken from the clock when SEED is missing,
+/// or None if the option is absent
+fn parse_random_start(mut args: impl Iterator<Item = String>) -> Option<u64> {
+    args.find(|arg| arg == "--random-start")?;
+    Some(
+        args.next()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(clock_seed),
+    )
+}
+
+/// Starts game `game_index` (0-based) of a "--random-start SEED" session,
+/// announcing the coin flip that picks its first player
+fn coin_flip_game(seed: u64, game_index: u64) -> Game {
+    let starter = series_starter(seed, game_index);
+    match starter {
+        Player::Human => println!("Coin flip: you move first"),
+        Player::AI => println!("Coin flip: the AI moves first"),
+    }
+    println!();
+    Game::new_with_starter(starter)
+}
+
+/// Picks the first player of game `game_index` (0-based) in a series: a
+/// coin flip of its own for each game, the first one seeded with `seed`
+fn series_starter(seed: u64, game_index: u64) -> Player {
+    choose_starter(seed.wrapping_add(game_index))
+}
+
+/// Flips a coin seeded with `seed` to pick the player moving first
+fn choose_starter(seed: u64) -> Player {
+    if Rng::new(seed).gen_range(2) == 0 {
+        Player::Human
+    } else {
+        Player::AI
+    }
+}
+
+/// Returns a seed that varies from one run to the next
+fn clock_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Command-line usage, printed when an option is invalid
+const USAGE: &str = "Usage: tic-tac-toe [--verbose] [--edit] [--blindfold] [--move-time SECS] \
+[--difficulty easy|medium|hard] [--blunder P] [--random-start [SEED]] [--best-of N]
+  easy: a random move 70% of the time; medium: looks 2 moves ahead; hard: never loses";
+
+/// Parses a --difficulty level name: "easy", "medium" or "hard"
+fn parse_difficulty(name: &str) -> Option<Difficulty> {
+    match name {
+        "easy" => Some(Difficulty::Easy),
+        "medium" => Some(Difficulty::Medium),
+        "hard" => Some(Difficulty::Hard),
+        _ => None,
+    }
+}
+
+/// Builds the AI from the "--difficulty LEVEL" and "--blunder P"
+/// command-line arguments, starting from `AI::with_difficulty`; --blunder
+/// overrides the level's blunder rate
+/// Without either option the AI plays perfectly (hard)
+/// Returns an error message for an unknown level or a P outside 0-1
+fn parse_ai(mut args: impl Iterator<Item = String>) -> Result<AI, String> {
+    let mut difficulty = Difficulty::Hard;
+    let mut blunder_rate = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--difficulty" => {
+                let level = args.next().unwrap_or_default();
+                difficulty = parse_difficulty(&level)
+                    .ok_or_else(|| format!("Unknown difficulty {level:?}"))?;
             }
+            "--blunder" => {
+                let value = args.next().unwrap_or_default();
+                match value.parse::<f64>() {
+                    Ok(rate) if (0.0..=1.0).contains(&rate) => blunder_rate = Some(rate),
+                    _ => return Err(format!("Invalid blunder probability {value:?}")),
+                }
+            }
+            _ => {}
         }
     }
+
+    let mut ai = AI::with_difficulty(difficulty);
+    if let Some(rate) = blunder_rate {
+        ai = ai.with_blunder_rate(rate);
+    }
+    // Blunders are random: vary them from one run to the next
+    Ok(ai.with_seed(clock_seed()))
+}
+
+/// Why a line typed at the move prompt was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputError {
+    /// Not a command, a number or a coordinate
+    Unrecognized,
+    /// A number or coordinate that is off the board
+    OutOfRange,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Unrecognized => write!(
+                f,
+                "Invalid input! Please enter a number between 1 and 9, \"row,col\" or a cell like \"b2\"."
+            ),
+            InputError::OutOfRange => write!(
+                f,
+                "Out of range! Positions go from 1 to 9, rows and columns from 1 to 3 (a to c)."
+            ),
+        }
+    }
+}
+
+/// Interprets a line typed at the move prompt
+/// Accepts commands, positions 1-9, "row,col" (1-indexed) and cells like "b2"
+fn parse_input(line: &str, board: &Board) -> Result<Input, InputError> {
+    let line = line.trim();
+
+    if line.eq_ignore_ascii_case("q") || line.eq_ignore_ascii_case("quit") {
+        return Ok(Input::Quit);
+    }
+
+    if line.eq_ignore_ascii_case("resign") {
+        return Ok(Input::Resign);
+    }
+
+    if line.eq_ignore_ascii_case("undo") {
+        return Ok(Input::Undo);
+    }
+
+    if line.eq_ignore_ascii_case("hint") {
+        return Ok(Input::Hint);
+    }
+
+    match parse_move_input(line, board) {
+        Err(InputError::Unrecognized) => parse_coordinate(line, board).map(Input::Move),
+        result => result.map(Input::Move),
+    }
+}
+
+/// Parses a position number (1-9 on a 3x3 board) into a 0-indexed position
+/// Surrounding whitespace and leading zeros are accepted ("05" is 5)
+fn parse_move_input(line: &str, board: &Board) -> Result<usize, InputError> {
+    let num: usize = line.trim().parse().map_err(|_| InputError::Unrecognized)?;
+    board.from_display_index(num).ok_or(InputError::OutOfRange)
+}
+
+/// Parses "row,col" (1-indexed) or a column letter and row number like "b2"
+/// into a position
+fn parse_coordinate(text: &str, board: &Board) -> Result<usize, InputError> {
+    let (row, col) = if let Some((row, col)) = text.split_once(',') {
+        let row: usize = row.trim().parse().map_err(|_| InputError::Unrecognized)?;
+        let col: usize = col.trim().parse().map_err(|_| InputError::Unrecognized)?;
+        (row, col)
+    } else {
+        let mut chars = text.chars();
+        let letter = chars
+            .next()
+            .filter(char::is_ascii_alphabetic)
+            .ok_or(InputError::Unrecognized)?;
+        let row: usize = chars
+            .as_str()
+            .parse()
+            .map_err(|_| InputError::Unrecognized)?;
+        let col = (letter.to_ascii_lowercase() as u8 - b'a') as usize + 1;
+        (row, col)
+    };
+
+    // Coordinates are 1-indexed for the player
+    let (row, col) = (row.checked_sub(1), col.checked_sub(1));
+    row.zip(col)
+        .and_then(|(row, col)| board.index_at(row, col))
+        .ok_or(InputError::OutOfRange)
+}
+
+/// A command typed in the board editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditCommand {
+    /// Cycle the cell at the given position (0-8): empty, X, O, empty...
+    Toggle(usize),
+    /// Set the side to move
+    SideToMove(Player),
+    /// Validate the position and start playing
+    Done,
+}
+
+/// Interprets a line typed in the board editor
+fn parse_edit_command(line: &str, board: &Board) -> Option<EditCommand> {
+    let line = line.trim();
+    if line.eq_ignore_ascii_case("done") {
+        return Some(EditCommand::Done);
+    }
+    if line.eq_ignore_ascii_case("x") {
+        return Some(EditCommand::SideToMove(Player::Human));
+    }
+    if line.eq_ignore_ascii_case("o") {
+        return Some(EditCommand::SideToMove(Player::AI));
+    }
+
+    let num = line.parse().ok()?;
+    board.from_display_index(num).map(EditCommand::Toggle)
+}
+
+/// Why the edited position can't be played from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditError {
+    /// The mark counts don't match the side to move
+    Position(InvalidPosition),
+    /// A player already has a complete line
+    AlreadyWon(Player),
+    /// Both players have a complete line
+    TwoWinners,
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::Position(error) => write!(f, "Invalid position: {error}."),
+            EditError::AlreadyWon(player) => {
+                write!(
+                    f,
+                    "{} has already won, nothing left to play.",
+                    player.symbol()
+                )
+            }
+            EditError::TwoWinners => write!(f, "Both players have a line, which can't happen."),
+        }
+    }
+}
+
+/// Position being set up in `--edit` mode
+#[derive(Debug, Clone)]
+struct Editor {
+    board: Board,
+    to_move: Player,
+}
+
+impl Editor {
+    /// Starts from an empty board with X to move
+    fn new() -> Self {
+        Editor {
+            board: Board::new(),
+            to_move: Player::Human,
+        }
+    }
+
+    /// Applies a toggle or side-to-move command
+    fn apply(&mut self, command: EditCommand) {
+        match command {
+            EditCommand::Toggle(position) => {
+                let next = match self.board.get(position) {
+                    Some(Cell::Empty) => Some(Player::Human),
+                    Some(Cell::Occupied(Player::Human)) => Some(Player::AI),
+                    _ => None,
+                };
+                self.board.undo_move(position);
+                if let Some(player) = next {
+                    self.board.make_move(position, player);
+                }
+            }
+            EditCommand::SideToMove(player) => self.to_move = player,
+            EditCommand::Done => {}
+        }
+    }
+
+    /// Checks the position can be played from and returns the game
+    fn finish(&self) -> Result<Game, EditError> {
+        let game = Game::from_board_checked(self.board.clone(), self.to_move)
+            .map_err(EditError::Position)?;
+
+        match (
+            game.check_winner(Player::Human),
+            game.check_winner(Player::AI),
+        ) {
+            (true, true) => Err(EditError::TwoWinners),
+            (true, false) => Err(EditError::AlreadyWon(Player::Human)),
+            (false, true) => Err(EditError::AlreadyWon(Player::AI)),
+            (false, false) => Ok(game),
+        }
+    }
+}
+
+/// Lets the user set up a position, reading commands from `reader`
+/// Returns the game to start from, or None if the input ends first
+fn run_editor<R: BufRead>(reader: &mut R) -> Option<Game> {
+    println!("Board editor: type a position (1-9) to cycle it between empty, X and O,");
+    println!("\"x\" or \"o\" to choose who moves, then \"done\" to start playing.");
+
+    let mut editor = Editor::new();
+    loop {
+        print!("{}", editor.board.display_with_glyphs(&Default::default()));
+        print!("{} to move > ", editor.to_move.symbol());
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if reader.read_line(&mut line).expect("Failed to read line") == 0 {
+            return None;
+        }
+
+        match parse_edit_command(&line, &editor.board) {
+            Some(EditCommand::Done) => match editor.finish() {
+                Ok(game) => return Some(game),
+                Err(error) => println!("{error}"),
+            },
+            Some(command) => editor.apply(command),
+            None => println!("Unknown command."),
+        }
+    }
+}
+
+/// Returns what is shown before each turn: the board, highlighting the AI's
+/// last move so the player sees what changed
+/// In blindfold mode the board is never drawn; the last move is announced
+/// in words instead, so the player can track the position mentally
+fn turn_display(game: &Game, blindfold: bool) -> String {
+    let board = game.board();
+    let last = game.history().last().copied();
+
+    if blindfold {
+        return last
+            .and_then(|position| match board.get(position) {
+                Some(Cell::Occupied(player)) => Some(format!(
+                    "{} played position {} ({})\n",
+                    player.symbol(),
+                    board.to_display_index(position),
+                    board.describe_move(position)
+                )),
+                _ => None,
+            })
+            .unwrap_or_default();
+    }
+
+    let grid = match last {
+        Some(last) if board.get(last) == Some(Cell::Occupied(Player::AI)) => {
+            board.display_highlight(last)
+        }
+        _ => board.display_with_glyphs(&GlyphSet::default()),
+    };
+    format!("\n\n{grid}\n\n")
 }
 
 /// Displays the position guide (how positions are numbered)
@@ -111,3 +769,392 @@ fn display_position_guide() {
     println!("  -----------");
     println!("   7 | 8 | 9");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits a command line into arguments, as passed to the option parsers
+    fn args(line: &str) -> impl Iterator<Item = String> {
+        line.split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_get_human_move_on_closed_input() {
+        let game = Game::new();
+        let mut reader: &[u8] = b"";
+        assert_eq!(get_human_move(&game, &mut reader), Input::Closed);
+    }
+
+    #[test]
+    fn test_get_human_move_eof_after_invalid_input() {
+        let game = Game::new();
+        // Invalid lines are skipped, then the stream ends
+        let mut reader: &[u8] = b"abc\n42\n";
+        assert_eq!(get_human_move(&game, &mut reader), Input::Closed);
+    }
+
+    #[test]
+    fn test_turn_display_blindfold() {
+        let mut game = Game::new();
+        assert_eq!(turn_display(&game, true), "");
+
+        game.make_move(4);
+        let shown = turn_display(&game, true);
+        assert_eq!(shown, "X played position 5 (center)\n");
+        assert!(!shown.contains('|'));
+
+        game.make_move(0);
+        assert_eq!(
+            turn_display(&game, true),
+            "O played position 1 (top-left corner)\n"
+        );
+    }
+
+    #[test]
+    fn test_turn_display_board() {
+        let mut game = Game::new();
+        game.make_move(4);
+        let shown = turn_display(&game, false);
+        assert!(shown.contains(&game.board().display_with_glyphs(&GlyphSet::default())));
+
+        // The AI's last move is highlighted
+        game.make_move(0);
+        assert!(turn_display(&game, false).contains("[O]"));
+    }
+
+    #[test]
+    fn test_undo_pair() {
+        let mut game = Game::from_moves_str("5 1 9 3").unwrap();
+        assert!(undo_pair(&mut game));
+        assert_eq!(game.history(), &[4, 0]);
+        assert_eq!(game.board().get(8), Some(Cell::Empty));
+        assert_eq!(game.board().get(2), Some(Cell::Empty));
+        assert_eq!(game.current_player(), Player::Human);
+
+        // Only the AI's opening move to take back: nothing happens
+        let mut game = Game::new_with_starter(Player::AI);
+        game.make_move(4);
+        assert!(!undo_pair(&mut game));
+        assert_eq!(game.history(), &[4]);
+    }
+
+    #[test]
+    fn test_parse_input_quit() {
+        let board = Board::new();
+        assert_eq!(parse_input("q\n", &board), Ok(Input::Quit));
+        assert_eq!(parse_input("quit", &board), Ok(Input::Quit));
+        assert_eq!(parse_input("QUIT", &board), Ok(Input::Quit));
+    }
+
+    #[test]
+    fn test_parse_input_moves_and_commands() {
+        let board = Board::new();
+        assert_eq!(parse_input("1", &board), Ok(Input::Move(0)));
+        assert_eq!(parse_input("9\n", &board), Ok(Input::Move(8)));
+        assert_eq!(parse_input("resign", &board), Ok(Input::Resign));
+        assert_eq!(parse_input("Undo", &board), Ok(Input::Undo));
+        assert_eq!(parse_input("hint", &board), Ok(Input::Hint));
+        assert_eq!(parse_input("0", &board), Err(InputError::OutOfRange));
+        assert_eq!(
+            parse_input("quitter", &board),
+            Err(InputError::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn test_parse_move_input() {
+        let board = Board::new();
+        assert_eq!(parse_move_input(" 5 ", &board), Ok(4));
+        assert_eq!(parse_move_input("05", &board), Ok(4));
+        assert_eq!(parse_move_input("9\n", &board), Ok(8));
+        assert_eq!(parse_move_input("10", &board), Err(InputError::OutOfRange));
+        assert_eq!(parse_move_input("00", &board), Err(InputError::OutOfRange));
+        assert_eq!(parse_move_input("x", &board), Err(InputError::Unrecognized));
+        assert_eq!(
+            parse_move_input("abc", &board),
+            Err(InputError::Unrecognized)
+        );
+        assert_eq!(
+            parse_move_input("5 6", &board),
+            Err(InputError::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn test_parse_input_coordinates() {
+        let board = Board::new();
+        assert_eq!(parse_input("2,3", &board), Ok(Input::Move(5)));
+        assert_eq!(parse_input(" 1, 1 ", &board), Ok(Input::Move(0)));
+        assert_eq!(parse_input("b2", &board), Ok(Input::Move(4)));
+        assert_eq!(parse_input("C3", &board), Ok(Input::Move(8)));
+        assert_eq!(parse_input("z9", &board), Err(InputError::OutOfRange));
+        assert_eq!(parse_input("4,1", &board), Err(InputError::OutOfRange));
+        assert_eq!(parse_input("0,1", &board), Err(InputError::OutOfRange));
+        assert_eq!(parse_input("b", &board), Err(InputError::Unrecognized));
+        assert_eq!(parse_input("2,x", &board), Err(InputError::Unrecognized));
+    }
+
+    #[test]
+    fn test_parse_edit_command() {
+        let board = Board::new();
+        assert_eq!(
+            parse_edit_command("5\n", &board),
+            Some(EditCommand::Toggle(4))
+        );
+        assert_eq!(
+            parse_edit_command("O", &board),
+            Some(EditCommand::SideToMove(Player::AI))
+        );
+        assert_eq!(
+            parse_edit_command(" done ", &board),
+            Some(EditCommand::Done)
+        );
+        assert_eq!(parse_edit_command("0", &board), None);
+        assert_eq!(parse_edit_command("b2", &board), None);
+    }
+
+    #[test]
+    fn test_editor_toggle_cycles_cell() {
+        let mut editor = Editor::new();
+        editor.apply(EditCommand::Toggle(4));
+        assert_eq!(editor.board[4], Cell::Occupied(Player::Human));
+        editor.apply(EditCommand::Toggle(4));
+        assert_eq!(editor.board[4], Cell::Occupied(Player::AI));
+        editor.apply(EditCommand::Toggle(4));
+        assert_eq!(editor.board, Board::new());
+    }
+
+    #[test]
+    fn test_editor_accepts_valid_position() {
+        let mut editor = Editor::new();
+        // X on 1 and 9, O on 5
+        editor.apply(EditCommand::Toggle(0));
+        editor.apply(EditCommand::Toggle(4));
+        editor.apply(EditCommand::Toggle(4));
+        editor.apply(EditCommand::Toggle(8));
+        editor.apply(EditCommand::SideToMove(Player::AI));
+        // Two X and one O: O must move
+        let game = editor.finish().unwrap();
+        assert_eq!(game.current_player(), Player::AI);
+        assert_eq!(game.state(), GameState::InProgress);
+        assert_eq!(game.available_moves(), vec![1, 2, 3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_editor_rejects_invalid_positions() {
+        // Two X and no O
+        let mut editor = Editor::new();
+        editor.apply(EditCommand::Toggle(0));
+        editor.apply(EditCommand::Toggle(1));
+        assert_eq!(
+            editor.finish().unwrap_err(),
+            EditError::Position(InvalidPosition::MarkCountMismatch { human: 2, ai: 0 })
+        );
+
+        // One X and no O, but X to move
+        let mut editor = Editor::new();
+        editor.apply(EditCommand::Toggle(0));
+        assert_eq!(
+            editor.finish().unwrap_err(),
+            EditError::Position(InvalidPosition::WrongPlayerToMove(Player::Human))
+        );
+
+        // X owns the top row
+        let mut editor = Editor::new();
+        for position in [0, 1, 2] {
+            editor.apply(EditCommand::Toggle(position));
+        }
+        for position in [3, 4] {
+            editor.apply(EditCommand::Toggle(position));
+            editor.apply(EditCommand::Toggle(position));
+        }
+        editor.apply(EditCommand::SideToMove(Player::AI));
+        assert_eq!(
+            editor.finish().unwrap_err(),
+            EditError::AlreadyWon(Player::Human)
+        );
+    }
+
+    #[test]
+    fn test_run_editor_retries_until_valid() {
+        // "done" on an invalid position is refused, then fixed
+        let mut reader: &[u8] = b"1\ndone\nhello\n5\n5\n3\no\ndone\n";
+        let game = run_editor(&mut reader).unwrap();
+        assert_eq!(game.board().cells_of(Player::Human), vec![0, 2]);
+        assert_eq!(game.board().cells_of(Player::AI), vec![4]);
+        assert_eq!(game.current_player(), Player::AI);
+
+        let mut reader: &[u8] = b"5\n";
+        assert!(run_editor(&mut reader).is_none());
+    }
+
+    #[test]
+    fn test_timed_best_move_matches_search() {
+        let mut game = Game::new();
+        game.make_move(0);
+        game.make_move(3);
+        game.make_move(1);
+        let ai = AI::new();
+
+        let (position, elapsed) = timed_best_move(&ai, &game).unwrap();
+        assert_eq!(Some(position), ai.find_best_move(&game));
+        assert!(elapsed >= Duration::ZERO);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(250)), "250 µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50 ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_get_human_move_reads_position() {
+        let game = Game::new();
+        let mut reader: &[u8] = b"5\n";
+        assert_eq!(get_human_move(&game, &mut reader), Input::Move(4));
+    }
+
+    /// Reader that waits before producing its content, like a slow player
+    struct SlowReader {
+        delay: Duration,
+        content: &'static [u8],
+    }
+
+    impl io::Read for SlowReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            thread::sleep(std::mem::take(&mut self.delay));
+            self.content.read(buf)
+        }
+    }
+
+    fn slow_reader(delay_ms: u64, content: &'static [u8]) -> TimedReader {
+        TimedReader::spawn(io::BufReader::new(SlowReader {
+            delay: Duration::from_millis(delay_ms),
+            content,
+        }))
+    }
+
+    #[test]
+    fn test_timed_reader_times_out() {
+        let reader = slow_reader(500, b"5\n");
+        let timeout = Duration::from_millis(20);
+        assert_eq!(reader.read_line(timeout), TimedLine::TimedOut);
+
+        // The late line is not lost
+        let timeout = Duration::from_secs(5);
+        assert_eq!(reader.read_line(timeout), TimedLine::Line("5\n".into()));
+        assert_eq!(reader.read_line(timeout), TimedLine::Closed);
+    }
+
+    #[test]
+    fn test_get_timed_human_move() {
+        let game = Game::new();
+
+        let reader = slow_reader(500, b"5\n");
+        let limit = Duration::from_millis(20);
+        assert_eq!(get_timed_human_move(&game, &reader, limit), Input::TimedOut);
+
+        let reader = slow_reader(0, b"abc\n5\n");
+        let limit = Duration::from_secs(5);
+        assert_eq!(get_timed_human_move(&game, &reader, limit), Input::Move(4));
+        assert_eq!(get_timed_human_move(&game, &reader, limit), Input::Closed);
+    }
+
+    #[test]
+    fn test_choose_starter() {
+        // A fixed seed always gives the same starter
+        for seed in 0..10 {
+            assert_eq!(choose_starter(seed), choose_starter(seed));
+        }
+        // Both outcomes come up across seeds
+        let starters: Vec<Player> = (0..20).map(choose_starter).collect();
+        assert!(starters.contains(&Player::Human));
+        assert!(starters.contains(&Player::AI));
+    }
+
+    #[test]
+    fn test_series_starter() {
+        // The first game keeps the single-game coin flip
+        assert_eq!(series_starter(7, 0), choose_starter(7));
+
+        // Each later game flips again, reproducibly
+        let starters: Vec<Player> = (0..20).map(|index| series_starter(7, index)).collect();
+        let again: Vec<Player> = (0..20).map(|index| series_starter(7, index)).collect();
+        assert_eq!(starters, again);
+        assert!(starters.contains(&Player::Human));
+        assert!(starters.contains(&Player::AI));
+    }
+
+    #[test]
+    fn test_parse_best_of() {
+        assert_eq!(parse_best_of(args("--best-of 3")), Some(3));
+        assert_eq!(parse_best_of(args("--verbose --best-of 5")), Some(5));
+        assert_eq!(parse_best_of(args("--best-of 0")), None);
+        assert_eq!(parse_best_of(args("--best-of three")), None);
+        assert_eq!(parse_best_of(args("--verbose")), None);
+    }
+
+    #[test]
+    fn test_parse_random_start() {
+        assert_eq!(parse_random_start(args("--random-start 42")), Some(42));
+        assert_eq!(
+            parse_random_start(args("--verbose --random-start 7")),
+            Some(7)
+        );
+        assert!(parse_random_start(args("--random-start")).is_some());
+        assert!(parse_random_start(args("--random-start --verbose")).is_some());
+        assert_eq!(parse_random_start(args("--verbose")), None);
+    }
+
+    #[test]
+    fn test_parse_move_time() {
+        assert_eq!(
+            parse_move_time(args("--move-time 10")),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            parse_move_time(args("--verbose --move-time 0.5")),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(parse_move_time(args("--verbose")), None);
+        assert_eq!(parse_move_time(args("--move-time")), None);
+        assert_eq!(parse_move_time(args("--move-time 0")), None);
+        assert_eq!(parse_move_time(args("--move-time abc")), None);
+    }
+
+    #[test]
+    fn test_parse_ai() {
+        assert_eq!(parse_difficulty("easy"), Some(Difficulty::Easy));
+        assert_eq!(parse_difficulty("insane"), None);
+
+        let parse = |line: &str| parse_ai(args(line));
+        let preset = |difficulty| AI::with_difficulty(difficulty).config();
+
+        let easy = parse("--difficulty easy").unwrap().config();
+        assert_eq!(easy.blunder_rate, preset(Difficulty::Easy).blunder_rate);
+        let medium = parse("--difficulty medium").unwrap().config();
+        assert_eq!(medium.search_depth, preset(Difficulty::Medium).search_depth);
+        assert_eq!(medium.blunder_rate, 0.0);
+        let hard = parse("--difficulty hard").unwrap().config();
+        assert_eq!(hard.search_depth, None);
+        assert_eq!(hard.blunder_rate, 0.0);
+        assert_eq!(parse("--verbose").unwrap().config().blunder_rate, 0.0);
+        assert_eq!(
+            parse("--difficulty easy --blunder 0.1")
+                .unwrap()
+                .config()
+                .blunder_rate,
+            0.1
+        );
+
+        assert!(parse("--difficulty insane").is_err());
+        assert!(parse("--difficulty").is_err());
+        assert!(parse("--blunder 2").is_err());
+        assert!(parse("--blunder often").is_err());
+    }
+}
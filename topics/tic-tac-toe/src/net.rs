@@ -0,0 +1,249 @@
+//! Human-vs-human play over TCP.
+//!
+//! Both sides keep their own `Game` and exchange moves as line-delimited
+//! positions (1-9 on a 3x3 board). Every received move is checked against
+//! the local game, so a misbehaving peer aborts the game with an error
+//! instead of corrupting the local state.
+
+use crate::game::{Game, GameState, MoveError};
+use crate::types::Player;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+/// Reasons a networked game is aborted
+#[derive(Debug)]
+pub enum NetError {
+    /// Reading from or writing to the connection failed
+    Io(io::Error),
+    /// The peer closed the connection before the game ended
+    Disconnected,
+    /// The peer sent a line that is not a position number
+    Malformed(String),
+    /// A move was rejected by the game rules
+    IllegalMove(MoveError),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetError::Io(error) => write!(f, "connection error: {error}"),
+            NetError::Disconnected => write!(f, "the other player disconnected"),
+            NetError::Malformed(line) => write!(f, "received an invalid move {line:?}"),
+            NetError::IllegalMove(error) => write!(f, "illegal move: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NetError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NetError {
+    fn from(error: io::Error) -> Self {
+        NetError::Io(error)
+    }
+}
+
+/// One end of a two-player game over TCP
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    local: Player,
+}
+
+impl Connection {
+    /// Wraps a connected stream; `local` is the side played on this end
+    pub fn new(stream: TcpStream, local: Player) -> io::Result<Self> {
+        Ok(Connection {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            local,
+        })
+    }
+
+    /// Returns the side played on this end of the connection
+    pub fn local_player(&self) -> Player {
+        self.local
+    }
+
+    /// Plays a local move on `game` and sends it to the peer
+    /// The move is only sent if it is legal
+    pub fn send_move(&mut self, game: &mut Game, position: usize) -> Result<GameState, NetError> {
+        let state = game.play(position).map_err(NetError::IllegalMove)?;
+        writeln!(self.writer, "{}", position + 1)?;
+        self.writer.flush()?;
+        Ok(state)
+    }
+
+    /// Waits for the peer's move and plays it on `game`
+    pub fn receive_move(&mut self, game: &mut Game) -> Result<GameState, NetError> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(NetError::Disconnected);
+        }
+
+        let position = match line.trim().parse::<usize>() {
+            Ok(num) if num >= 1 => num - 1,
+            _ => return Err(NetError::Malformed(line.trim().to_string())),
+        };
+        game.play(position).map_err(NetError::IllegalMove)
+    }
+
+    /// Plays `game` to the end, alternating local and remote moves
+    /// `choose_move` picks the local player's moves; returning None stops
+    /// the game early and returns its current state
+    pub fn play<F>(&mut self, game: &mut Game, mut choose_move: F) -> Result<GameState, NetError>
+    where
+        F: FnMut(&Game) -> Option<usize>,
+    {
+        while game.state() == GameState::InProgress {
+            if game.current_player() == self.local {
+                let Some(position) = choose_move(game) else {
+                    break;
+                };
+                self.send_move(game, position)?;
+            } else {
+                self.receive_move(game)?;
+            }
+        }
+
+        Ok(game.state())
+    }
+}
+
+/// Listens on `addr` and waits for one opponent to connect
+/// The host plays X (the Human side) and moves first
+pub fn host(addr: impl ToSocketAddrs) -> Result<Connection, NetError> {
+    let listener = TcpListener::bind(addr)?;
+    accept(&listener)
+}
+
+/// Waits for one opponent on an already bound listener, as the host (X)
+pub fn accept(listener: &TcpListener) -> Result<Connection, NetError> {
+    let (stream, _) = listener.accept()?;
+    Ok(Connection::new(stream, Player::Human)?)
+}
+
+/// Connects to a host at `addr`
+/// The joining side plays O (the AI side) and moves second
+pub fn connect(addr: impl ToSocketAddrs) -> Result<Connection, NetError> {
+    let stream = TcpStream::connect(addr)?;
+    Ok(Connection::new(stream, Player::AI)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// Returns a connected (host, guest) pair over the loopback interface
+    fn loopback() -> (Connection, Connection) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let guest = thread::spawn(move || connect(addr).unwrap());
+        let host = accept(&listener).unwrap();
+        (host, guest.join().unwrap())
+    }
+
+    /// Returns a move chooser that plays the given positions in order
+    fn scripted(moves: Vec<usize>) -> impl FnMut(&Game) -> Option<usize> {
+        let mut moves = moves.into_iter();
+        move |_| moves.next()
+    }
+
+    #[test]
+    fn test_scripted_game_over_loopback() {
+        let (mut host, mut guest) = loopback();
+        assert_eq!(host.local_player(), Player::Human);
+        assert_eq!(guest.local_player(), Player::AI);
+
+        // X takes the top row while O plays the middle row
+        let guest_side = thread::spawn(move || {
+            let mut game = Game::new();
+            let state = guest.play(&mut game, scripted(vec![3, 4]));
+            (state.unwrap(), game)
+        });
+
+        let mut game = Game::new();
+        let state = host.play(&mut game, scripted(vec![0, 1, 2])).unwrap();
+        let (guest_state, guest_game) = guest_side.join().unwrap();
+
+        assert_eq!(state, GameState::Won(Player::Human));
+        assert_eq!(guest_state, state);
+        assert_eq!(guest_game.history(), &[0, 3, 1, 4, 2]);
+        assert_eq!(guest_game.board(), game.board());
+    }
+
+    #[test]
+    fn test_illegal_remote_move_aborts() {
+        let (mut host, guest) = loopback();
+        let mut game = Game::new();
+        host.send_move(&mut game, 4).unwrap();
+
+        // The guest answers on the occupied center
+        let mut raw = guest.writer;
+        writeln!(raw, "5").unwrap();
+        assert!(matches!(
+            host.receive_move(&mut game),
+            Err(NetError::IllegalMove(MoveError::CellOccupied(4)))
+        ));
+        assert_eq!(game.history(), &[4]);
+        assert_eq!(game.current_player(), Player::AI);
+    }
+
+    #[test]
+    fn test_malformed_remote_move_aborts() {
+        let (mut host, guest) = loopback();
+        let mut game = Game::new();
+        game.make_move(0);
+
+        let mut raw = guest.writer;
+        writeln!(raw, "center").unwrap();
+        writeln!(raw, "10").unwrap();
+        assert!(matches!(
+            host.receive_move(&mut game),
+            Err(NetError::Malformed(line)) if line == "center"
+        ));
+        assert!(matches!(
+            host.receive_move(&mut game),
+            Err(NetError::IllegalMove(MoveError::OutOfBounds(9)))
+        ));
+        assert_eq!(game.history(), &[0]);
+    }
+
+    #[test]
+    fn test_disconnect_aborts() {
+        let (mut host, guest) = loopback();
+        let mut game = Game::new();
+        game.make_move(0);
+
+        drop(guest);
+        assert!(matches!(
+            host.receive_move(&mut game),
+            Err(NetError::Disconnected)
+        ));
+    }
+
+    #[test]
+    fn test_illegal_local_move_is_not_sent() {
+        let (mut host, mut guest) = loopback();
+        let mut game = Game::new();
+
+        assert!(matches!(
+            host.send_move(&mut game, 9),
+            Err(NetError::IllegalMove(MoveError::OutOfBounds(9)))
+        ));
+        host.send_move(&mut game, 0).unwrap();
+
+        // Only the legal move reached the guest
+        let mut guest_game = Game::new();
+        guest.receive_move(&mut guest_game).unwrap();
+        assert_eq!(guest_game.history(), &[0]);
+    }
+}
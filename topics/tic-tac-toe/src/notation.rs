@@ -0,0 +1,924 @@
+//! Textual game notations for sharing and reloading games.
+//!
+//! The move-list notation numbers each pair of moves, PGN style, and uses
+//! 1-indexed positions: `1. X:5 O:1 2. X:3 O:7 3. X:9`.
+//!
+//! The compact board format is one character per cell in row-major order,
+//! with dots for empty cells: `X.O.X..OX`.
+//!
+//! Saved games add the side to move to the compact board, after a bar:
+//! `X.O.X..OX|O`.
+//!
+//! URL fragments pack a game into URL-safe characters: the board size, the
+//! starting side, then one base-36 digit per 0-indexed move: `3X408`.
+
+use crate::board::{self, Board};
+use crate::game::{Game, GameState, InvalidPosition};
+use crate::types::{Cell, Player};
+use std::fmt;
+
+/// Errors produced when parsing a textual game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A token that doesn't fit the notation at that point
+    InvalidToken(String),
+    /// A move number that doesn't follow the previous one
+    MoveNumber { expected: usize, found: usize },
+    /// The move at this index (0-based) was made by the wrong player
+    OutOfTurn(usize),
+    /// The move at this index (0-based) is not legal in the position
+    IllegalMove(usize),
+    /// The input doesn't have one character per cell
+    WrongLength { expected: usize, found: usize },
+    /// A character that doesn't stand for a cell
+    InvalidCharacter(char),
+    /// A board size this engine doesn't support
+    UnsupportedSize(usize),
+    /// A board that can't occur with the given player to move
+    InvalidPosition(InvalidPosition),
+    /// JSON input that doesn't follow the expected structure
+    MalformedJson(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidToken(token) => write!(f, "unexpected token '{token}'"),
+            ParseError::MoveNumber { expected, found } => {
+                write!(f, "expected move number {expected}, found {found}")
+            }
+            ParseError::OutOfTurn(index) => write!(f, "move {} is out of turn", index + 1),
+            ParseError::IllegalMove(index) => write!(f, "move {} is illegal", index + 1),
+            ParseError::WrongLength { expected, found } => {
+                write!(f, "expected {expected} cells, found {found}")
+            }
+            ParseError::InvalidCharacter(c) => write!(f, "invalid cell character '{c}'"),
+            ParseError::UnsupportedSize(size) => write!(f, "unsupported board size {size}"),
+            ParseError::InvalidPosition(error) => write!(f, "invalid position: {error}"),
+            ParseError::MalformedJson(reason) => write!(f, "malformed JSON: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Game {
+    /// Exports the moves played so far as move-list notation
+    /// e.g. `1. X:5 O:1 2. X:3`; marks set up with `from_board` are not included
+    pub fn to_notation(&self) -> String {
+        let mut tokens = Vec::new();
+
+        for (idx, &position) in self.history().iter().enumerate() {
+            if idx % 2 == 0 {
+                tokens.push(format!("{}.", idx / 2 + 1));
+            }
+            let symbol = self.board().get(position).map_or(' ', |cell| cell.symbol());
+            tokens.push(format!("{}:{}", symbol, position + 1));
+        }
+
+        tokens.join(" ")
+    }
+
+    /// Rebuilds a game from move-list notation by replaying every move
+    /// from an empty board; the first move's symbol decides who started
+    pub fn from_notation(s: &str) -> Result<Game, ParseError> {
+        let moves = parse_move_list(s)?;
+
+        let starter = moves.first().map_or(Player::Human, |&(player, _)| player);
+        let mut game = Game::from_board(Board::new(), starter);
+
+        for (idx, &(player, position)) in moves.iter().enumerate() {
+            if player != game.current_player() {
+                return Err(ParseError::OutOfTurn(idx));
+            }
+            if !game.make_move(position) {
+                return Err(ParseError::IllegalMove(idx));
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Exports the board size and the moves played so far as JSON, e.g.
+    /// `{"size":3,"moves":[4,0,8],"starter":"X"}` with 0-indexed positions
+    /// Like `to_notation`, marks set up with `from_board` are not included
+    pub fn to_move_json(&self) -> String {
+        let moves: Vec<String> = self.history().iter().map(usize::to_string).collect();
+        let starter = match self.history().first().and_then(|&p| self.board().get(p)) {
+            Some(Cell::Occupied(player)) => player,
+            _ => self.current_player(),
+        };
+        format!(
+            "{{\"size\":{},\"moves\":[{}],\"starter\":\"{}\"}}",
+            self.board().size(),
+            moves.join(","),
+            starter.symbol()
+        )
+    }
+
+    /// Rebuilds a game from `to_move_json` output by replaying its moves
+    /// from an empty board; keys may come in any order, and `size` may be
+    /// left out for a 3x3 board
+    pub fn from_move_json(s: &str) -> Result<Game, ParseError> {
+        let (size, moves, starter) = parse_move_json(s)?;
+
+        let board = Board::with_size(size).map_err(|_| ParseError::UnsupportedSize(size))?;
+        let mut game = Game::from_board(board, starter);
+        for (idx, position) in moves.into_iter().enumerate() {
+            if !game.make_move(position) {
+                return Err(ParseError::IllegalMove(idx));
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Builds a 3x3 game from a space-separated list of 1-indexed moves such
+    /// as `5 1 9 3`, played alternately with X first
+    /// A concise format for test fixtures and documentation examples
+    pub fn from_moves_str(s: &str) -> Result<Game, ParseError> {
+        let mut game = Game::new();
+
+        for (idx, token) in s.split_whitespace().enumerate() {
+            let num = token
+                .parse()
+                .map_err(|_| ParseError::InvalidToken(token.to_string()))?;
+            let legal = game
+                .board()
+                .from_display_index(num)
+                .is_some_and(|position| game.make_move(position));
+            if !legal {
+                return Err(ParseError::IllegalMove(idx));
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Exports the moves played so far as a URL-safe fragment such as
+    /// `3X408`: board size, starting side, then each 0-indexed position as
+    /// one base-36 digit
+    /// Like `to_notation`, marks set up with `from_board` are not included
+    pub fn to_url_fragment(&self) -> String {
+        let starter = match self.history().first().and_then(|&p| self.board().get(p)) {
+            Some(Cell::Occupied(player)) => player,
+            _ => self.current_player(),
+        };
+
+        let mut fragment = format!("{}{}", self.board().size(), starter.symbol());
+        for &position in self.history() {
+            // Positions stay below 36 as boards are at most 6x6
+            fragment.extend(char::from_digit(position as u32, 36));
+        }
+        fragment
+    }
+
+    /// Rebuilds a game from `to_url_fragment` output by replaying its moves
+    /// from an empty board
+    pub fn from_url_fragment(s: &str) -> Result<Game, ParseError> {
+        let mut chars = s.chars();
+        let (Some(size), Some(side)) = (chars.next(), chars.next()) else {
+            return Err(ParseError::InvalidToken(s.to_string()));
+        };
+
+        let size = size
+            .to_digit(10)
+            .ok_or(ParseError::InvalidCharacter(size))? as usize;
+        let board = Board::with_size(size).map_err(|_| ParseError::UnsupportedSize(size))?;
+        let starter = match side {
+            'X' => Player::Human,
+            'O' => Player::AI,
+            _ => return Err(ParseError::InvalidCharacter(side)),
+        };
+
+        let mut game = Game::from_board(board, starter);
+        for (idx, c) in chars.enumerate() {
+            let position = c.to_digit(36).ok_or(ParseError::InvalidCharacter(c))?;
+            if !game.make_move(position as usize) {
+                return Err(ParseError::IllegalMove(idx));
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Loads a puzzle written as a compact 3x3 board and the side to move,
+    /// e.g. `XX.OO.... X`
+    /// The mark counts must allow that side to be moving
+    pub fn from_puzzle(s: &str) -> Result<Game, ParseError> {
+        let mut tokens = s.split_whitespace();
+        let (Some(cells), Some(side), None) = (tokens.next(), tokens.next(), tokens.next()) else {
+            return Err(ParseError::InvalidToken(s.trim().to_string()));
+        };
+
+        let board = Board::from_compact(cells, 3)?;
+        let player = match side {
+            "X" => Player::Human,
+            "O" => Player::AI,
+            _ => return Err(ParseError::InvalidToken(side.to_string())),
+        };
+
+        Game::from_board_checked(board, player).map_err(ParseError::InvalidPosition)
+    }
+
+    /// Saves the position as its compact board and the side to move, e.g.
+    /// `X.O.X..OX|O`, to resume it later or share it
+    /// A game finished on the board records the side that would move next;
+    /// the move history and a resignation are not saved
+    /// Only the standard rules are saved (a line spans the board), so a game
+    /// from `Game::with_win_length` does not load back with its own rules
+    pub fn serialize(&self) -> String {
+        let finished = self.state() != GameState::InProgress && self.resigned_by().is_none();
+        let to_move = if finished {
+            // The last mover keeps the turn once the game is over
+            self.current_player().opponent()
+        } else {
+            self.current_player()
+        };
+        format!("{}|{}", self.board().to_compact(), to_move.symbol())
+    }
+
+    /// Loads a position saved by `serialize`, the board size following
+    /// from the number of cells; `-` is also read as an empty cell
+    /// The mark counts must allow the side to move, and that side must not
+    /// already own a line; a won or full board loads as a finished game
+    pub fn deserialize(s: &str) -> Result<Game, ParseError> {
+        let Some((cells, side)) = s.trim().split_once('|') else {
+            return Err(ParseError::InvalidToken(s.trim().to_string()));
+        };
+
+        let cells = cells.replace('-', ".");
+        let found = cells.chars().count();
+        let size = (1..=board::MAX_SIZE)
+            .min_by_key(|size| (size * size).abs_diff(found))
+            .unwrap_or(3);
+        let board = Board::from_compact(&cells, size)?;
+        let player = match side {
+            "X" => Player::Human,
+            "O" => Player::AI,
+            _ => return Err(ParseError::InvalidToken(side.to_string())),
+        };
+
+        let game = Game::from_board_checked(board, player).map_err(ParseError::InvalidPosition)?;
+        // The side to move can't already own a line: play stops at a win
+        if game.check_winner(player) {
+            return Err(ParseError::InvalidPosition(
+                InvalidPosition::WrongPlayerToMove(player),
+            ));
+        }
+        // A position ended by the last move is left with its mover to play,
+        // as `make_move` leaves it
+        let last_mover = player.opponent();
+        if game.check_winner(last_mover) || game.board().is_full() {
+            return Ok(Game::from_board(game.board().clone(), last_mover));
+        }
+        Ok(game)
+    }
+}
+
+impl Board {
+    /// Returns the board as a one-line string, e.g. `X.O.X..OX`
+    pub fn to_compact(&self) -> String {
+        self.cells()
+            .iter()
+            .map(|cell| match cell {
+                Cell::Empty => '.',
+                Cell::Occupied(player) => player.symbol(),
+            })
+            .collect()
+    }
+
+    /// Parses a board written by `to_compact` for a `size` x `size` grid
+    /// Sizes accepted by `Board::with_size` are supported
+    pub fn from_compact(s: &str, size: usize) -> Result<Board, ParseError> {
+        let mut board = Board::with_size(size).map_err(|_| ParseError::UnsupportedSize(size))?;
+
+        let found = s.chars().count();
+        if found != size * size {
+            return Err(ParseError::WrongLength {
+                expected: size * size,
+                found,
+            });
+        }
+
+        for (position, c) in s.chars().enumerate() {
+            match c {
+                '.' => {}
+                'X' => {
+                    board.make_move(position, Player::Human);
+                }
+                'O' => {
+                    board.make_move(position, Player::AI);
+                }
+                _ => return Err(ParseError::InvalidCharacter(c)),
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+/// Reads the board `size` (3 if absent), `moves` array and `starter`
+/// symbol of a JSON move list
+fn parse_move_json(s: &str) -> Result<(usize, Vec<usize>, Player), ParseError> {
+    let mut json = JsonCursor {
+        chars: s.chars().peekable(),
+    };
+    let mut size = None;
+    let mut moves = None;
+    let mut starter = None;
+
+    json.expect('{')?;
+    loop {
+        match json.next_char() {
+            Some('}') if size.is_none() && moves.is_none() && starter.is_none() => break,
+            Some('"') => {}
+            _ => return Err(json.error("expected a key")),
+        }
+        let key = json.string()?;
+        json.expect(':')?;
+        match key.as_str() {
+            "size" if size.is_none() => size = Some(json.number()?),
+            "moves" if moves.is_none() => moves = Some(json.number_array()?),
+            "starter" if starter.is_none() => {
+                json.expect('"')?;
+                starter = match json.string()?.as_str() {
+                    "X" => Some(Player::Human),
+                    "O" => Some(Player::AI),
+                    _ => return Err(json.error("starter must be \"X\" or \"O\"")),
+                };
+            }
+            _ => return Err(json.error(&format!("unexpected key \"{key}\""))),
+        }
+        match json.next_char() {
+            Some(',') => continue,
+            Some('}') => break,
+            _ => return Err(json.error("expected ',' or '}'")),
+        }
+    }
+    if json.next_char().is_some() {
+        return Err(json.error("trailing characters"));
+    }
+
+    match (moves, starter) {
+        (Some(moves), Some(starter)) => Ok((size.unwrap_or(3), moves, starter)),
+        (None, _) => Err(json.error("missing \"moves\"")),
+        (_, None) => Err(json.error("missing \"starter\"")),
+    }
+}
+
+/// Just enough of a JSON reader for move lists: an object holding
+/// non-negative integers, an array of them and a string, with no escape
+/// sequences
+struct JsonCursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl JsonCursor<'_> {
+    /// Returns the next character that isn't whitespace
+    fn next_char(&mut self) -> Option<char> {
+        self.chars.by_ref().find(|c| !c.is_whitespace())
+    }
+
+    /// Consumes the next non-whitespace character, which must be `expected`
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.next_char() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{expected}'"))),
+        }
+    }
+
+    /// Reads the rest of a string whose opening quote was consumed
+    fn string(&mut self) -> Result<String, ParseError> {
+        let mut text = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(text),
+                Some('\\') | None => return Err(self.error("unsupported or unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    /// Reads a non-negative integer
+    fn number(&mut self) -> Result<usize, ParseError> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut digits = String::new();
+        while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        digits.parse().map_err(|_| self.error("expected a number"))
+    }
+
+    /// Reads an array of non-negative integers, e.g. `[4, 0, 8]`
+    fn number_array(&mut self) -> Result<Vec<usize>, ParseError> {
+        self.expect('[')?;
+        let mut numbers = Vec::new();
+        loop {
+            while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+            let mut digits = String::new();
+            while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                digits.push(c);
+            }
+            if !digits.is_empty() {
+                numbers.push(
+                    digits
+                        .parse()
+                        .map_err(|_| self.error("number out of range"))?,
+                );
+            }
+            match self.next_char() {
+                Some(',') if !digits.is_empty() => continue,
+                Some(']') if !digits.is_empty() || numbers.is_empty() => return Ok(numbers),
+                _ => return Err(self.error("expected a list of positions")),
+            }
+        }
+    }
+
+    /// Builds a `MalformedJson` error giving `reason`
+    fn error(&self, reason: &str) -> ParseError {
+        ParseError::MalformedJson(reason.to_string())
+    }
+}
+
+/// Splits move-list notation into (player, 0-indexed position) pairs,
+/// checking move numbers but not legality
+fn parse_move_list(s: &str) -> Result<Vec<(Player, usize)>, ParseError> {
+    let mut moves = Vec::new();
+    let mut turn = 0;
+    let mut moves_in_turn = 0;
+
+    for token in s.split_whitespace() {
+        let invalid = || ParseError::InvalidToken(token.to_string());
+
+        if let Some(number) = token.strip_suffix('.') {
+            let number: usize = number.parse().map_err(|_| invalid())?;
+            // Every turn but the last one holds both players' moves
+            if turn > 0 && moves_in_turn != 2 {
+                return Err(invalid());
+            }
+            turn += 1;
+            if number != turn {
+                return Err(ParseError::MoveNumber {
+                    expected: turn,
+                    found: number,
+                });
+            }
+            moves_in_turn = 0;
+        } else {
+            if turn == 0 || moves_in_turn == 2 {
+                return Err(invalid());
+            }
+            let (symbol, position) = token.split_once(':').ok_or_else(invalid)?;
+            let player = match symbol {
+                "X" => Player::Human,
+                "O" => Player::AI,
+                _ => return Err(invalid()),
+            };
+            let position: usize = position.parse().map_err(|_| invalid())?;
+            if !(1..=9).contains(&position) {
+                return Err(invalid());
+            }
+            moves.push((player, position - 1));
+            moves_in_turn += 1;
+        }
+    }
+
+    // A trailing move number with no move after it
+    if turn > 0 && moves_in_turn == 0 {
+        return Err(ParseError::InvalidToken(format!("{turn}.")));
+    }
+
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use crate::game::GameState;
+
+    #[test]
+    fn test_notation_round_trip() {
+        let mut game = Game::new();
+        for position in [4, 0, 2, 6, 3] {
+            game.make_move(position);
+        }
+
+        let notation = game.to_notation();
+        assert_eq!(notation, "1. X:5 O:1 2. X:3 O:7 3. X:4");
+
+        let parsed = Game::from_notation(&notation).unwrap();
+        assert_eq!(parsed, game);
+        assert_eq!(parsed.to_notation(), notation);
+    }
+
+    #[test]
+    fn test_notation_finished_game() {
+        let mut game = Game::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+
+        let parsed = Game::from_notation(&game.to_notation()).unwrap();
+        assert_eq!(parsed.state(), GameState::Won(Player::Human));
+        assert_eq!(parsed.board().get(2), Some(Cell::Occupied(Player::Human)));
+    }
+
+    #[test]
+    fn test_notation_empty_game() {
+        let game = Game::new();
+        assert_eq!(game.to_notation(), "");
+        assert_eq!(Game::from_notation("").unwrap(), game);
+    }
+
+    #[test]
+    fn test_moves_str() {
+        let game = Game::from_moves_str("5 1 9 3").unwrap();
+        assert_eq!(game.history(), &[4, 0, 8, 2]);
+        assert_eq!(
+            game.board(),
+            &BoardBuilder::new().x(4).o(0).x(8).o(2).build()
+        );
+        assert_eq!(game.current_player(), Player::Human);
+
+        // Extra whitespace is fine, and no moves is the empty game
+        assert_eq!(Game::from_moves_str("  5\t1 9  3 ").unwrap(), game);
+        assert_eq!(Game::from_moves_str("").unwrap(), Game::new());
+    }
+
+    #[test]
+    fn test_moves_str_errors() {
+        assert_eq!(
+            Game::from_moves_str("5 1 10"),
+            Err(ParseError::IllegalMove(2))
+        );
+        assert_eq!(Game::from_moves_str("0"), Err(ParseError::IllegalMove(0)));
+        assert_eq!(Game::from_moves_str("5 5"), Err(ParseError::IllegalMove(1)));
+        assert_eq!(
+            Game::from_moves_str("5 x"),
+            Err(ParseError::InvalidToken("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_url_fragment_round_trip() {
+        let game = Game::from_notation("1. X:5 O:1 2. X:9 O:3").unwrap();
+        let fragment = game.to_url_fragment();
+        assert_eq!(fragment, "3X4082");
+        assert_eq!(Game::from_url_fragment(&fragment), Ok(game));
+
+        let mut game = Game::from_board(Board::with_size(6).unwrap(), Player::AI);
+        for position in [35, 0, 17] {
+            game.make_move(position);
+        }
+        let fragment = game.to_url_fragment();
+        assert_eq!(fragment, "6Oz0h");
+        assert_eq!(Game::from_url_fragment(&fragment), Ok(game));
+
+        assert_eq!(Game::from_url_fragment("3X"), Ok(Game::new()));
+    }
+
+    #[test]
+    fn test_url_fragment_is_url_safe() {
+        let mut game = Game::from_board(Board::with_size(6).unwrap(), Player::Human);
+        for position in 0..36 {
+            game.make_move(position);
+        }
+        // Only unreserved characters, which never need percent-encoding
+        assert!(
+            game.to_url_fragment()
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c))
+        );
+    }
+
+    #[test]
+    fn test_url_fragment_parse_errors() {
+        assert_eq!(
+            Game::from_url_fragment("3"),
+            Err(ParseError::InvalidToken("3".to_string()))
+        );
+        assert_eq!(
+            Game::from_url_fragment("9X0"),
+            Err(ParseError::UnsupportedSize(9))
+        );
+        assert_eq!(
+            Game::from_url_fragment("3Z0"),
+            Err(ParseError::InvalidCharacter('Z'))
+        );
+        assert_eq!(
+            Game::from_url_fragment("3X4%"),
+            Err(ParseError::InvalidCharacter('%'))
+        );
+        assert_eq!(
+            Game::from_url_fragment("3X44"),
+            Err(ParseError::IllegalMove(1))
+        );
+        assert_eq!(
+            Game::from_url_fragment("3X9"),
+            Err(ParseError::IllegalMove(0))
+        );
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let board = BoardBuilder::new().x(0).o(2).x(4).o(7).x(8).build();
+        let compact = board.to_compact();
+
+        assert_eq!(compact, "X.O.X..OX");
+        assert_eq!(Board::from_compact(&compact, 3), Ok(board));
+        assert_eq!(Board::new().to_compact(), ".........");
+
+        let mut board = Board::with_size(4).unwrap();
+        board.make_move(5, Player::Human);
+        assert_eq!(board.to_compact(), ".....X..........");
+        assert_eq!(Board::from_compact(&board.to_compact(), 4), Ok(board));
+    }
+
+    #[test]
+    fn test_compact_parse_errors() {
+        assert_eq!(
+            Board::from_compact("X.O.X", 3),
+            Err(ParseError::WrongLength {
+                expected: 9,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Board::from_compact("X.O.X..OXX", 3),
+            Err(ParseError::WrongLength {
+                expected: 9,
+                found: 10
+            })
+        );
+        assert_eq!(
+            Board::from_compact("X.O.Z..OX", 3),
+            Err(ParseError::InvalidCharacter('Z'))
+        );
+        assert_eq!(
+            Board::from_compact("X.O.X..OX", 4),
+            Err(ParseError::WrongLength {
+                expected: 16,
+                found: 9
+            })
+        );
+        assert_eq!(
+            Board::from_compact("", 0),
+            Err(ParseError::UnsupportedSize(0))
+        );
+        assert_eq!(
+            Board::from_compact("X.O.X..OX", 7),
+            Err(ParseError::UnsupportedSize(7))
+        );
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let game = Game::from_moves_str("5 1 9").unwrap();
+        let saved = game.serialize();
+        assert_eq!(saved, "O...X...X|O");
+
+        let loaded = Game::deserialize(&saved).unwrap();
+        assert_eq!(loaded.board(), game.board());
+        assert_eq!(loaded.current_player(), Player::AI);
+        assert_eq!(loaded.state(), GameState::InProgress);
+        assert_eq!(loaded.serialize(), saved);
+
+        assert_eq!(Game::deserialize(&Game::new().serialize()), Ok(Game::new()));
+        // Empty cells may also be written as '-'
+        assert_eq!(
+            Game::deserialize("XXO----O-|X").unwrap().serialize(),
+            "XXO....O.|X"
+        );
+
+        let mut game = Game::from_board(Board::with_size(4).unwrap(), Player::AI);
+        game.make_move(5);
+        let loaded = Game::deserialize(&game.serialize()).unwrap();
+        assert_eq!(loaded.board(), game.board());
+        assert_eq!(loaded.current_player(), Player::Human);
+    }
+
+    #[test]
+    fn test_serialize_finished_games() {
+        let won = Game::from_moves_str("1 4 2 5 3").unwrap();
+        assert_eq!(won.serialize(), "XXXOO....|O");
+        let loaded = Game::deserialize(&won.serialize()).unwrap();
+        assert_eq!(loaded.state(), GameState::Won(Player::Human));
+        assert_eq!(loaded.current_player(), won.current_player());
+
+        let drawn = Game::from_moves_str("1 5 9 2 8 7 3 6 4").unwrap();
+        assert_eq!(drawn.state(), GameState::Draw);
+        let loaded = Game::deserialize(&drawn.serialize()).unwrap();
+        assert_eq!(loaded.state(), GameState::Draw);
+        assert_eq!(loaded.board(), drawn.board());
+        assert_eq!(loaded.current_player(), drawn.current_player());
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        assert_eq!(
+            Game::deserialize("X.O.X..OXX|O"),
+            Err(ParseError::WrongLength {
+                expected: 9,
+                found: 10
+            })
+        );
+        assert_eq!(
+            Game::deserialize("X.O#X..OX|O"),
+            Err(ParseError::InvalidCharacter('#'))
+        );
+        assert_eq!(
+            Game::deserialize("X.O.X..OX"),
+            Err(ParseError::InvalidToken("X.O.X..OX".to_string()))
+        );
+        assert_eq!(
+            Game::deserialize("X.O.X..OX|Z"),
+            Err(ParseError::InvalidToken("Z".to_string()))
+        );
+        // Three X's against one O
+        assert_eq!(
+            Game::deserialize("XX..X...O|O"),
+            Err(ParseError::InvalidPosition(
+                InvalidPosition::MarkCountMismatch { human: 3, ai: 1 }
+            ))
+        );
+        // X has the extra mark, so O must be to move
+        assert_eq!(
+            Game::deserialize("X........|X"),
+            Err(ParseError::InvalidPosition(
+                InvalidPosition::WrongPlayerToMove(Player::Human)
+            ))
+        );
+        // X already owns the top row, so O could not have replied
+        assert_eq!(
+            Game::deserialize("XXXOO.O..|X"),
+            Err(ParseError::InvalidPosition(
+                InvalidPosition::WrongPlayerToMove(Player::Human)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_move_json_round_trip() {
+        let mut game = Game::new();
+        for position in [4, 0, 8, 2] {
+            game.make_move(position);
+        }
+
+        let json = game.to_move_json();
+        assert_eq!(json, r#"{"size":3,"moves":[4,0,8,2],"starter":"X"}"#);
+        assert_eq!(Game::from_move_json(&json), Ok(game));
+
+        let empty = Game::from_board(Board::new(), Player::AI);
+        assert_eq!(
+            empty.to_move_json(),
+            r#"{"size":3,"moves":[],"starter":"O"}"#
+        );
+        assert_eq!(Game::from_move_json(&empty.to_move_json()), Ok(empty));
+    }
+
+    #[test]
+    fn test_move_json_round_trip_4x4() {
+        let mut game = Game::from_board(Board::with_size(4).unwrap(), Player::Human);
+        for position in [5, 0, 15, 10] {
+            game.make_move(position);
+        }
+
+        let json = game.to_move_json();
+        assert_eq!(json, r#"{"size":4,"moves":[5,0,15,10],"starter":"X"}"#);
+        assert_eq!(Game::from_move_json(&json), Ok(game));
+
+        assert_eq!(
+            Game::from_move_json(r#"{"size":7,"moves":[],"starter":"X"}"#),
+            Err(ParseError::UnsupportedSize(7))
+        );
+    }
+
+    #[test]
+    fn test_move_json_accepts_whitespace_and_key_order() {
+        let game =
+            Game::from_move_json(" { \"starter\" : \"O\",\n \"moves\": [ 4 , 0 ] } ").unwrap();
+        assert_eq!(game.history(), &[4, 0]);
+        assert_eq!(game.board()[4], Cell::Occupied(Player::AI));
+    }
+
+    #[test]
+    fn test_malformed_move_json() {
+        let malformed = [
+            "",
+            "[4, 0]",
+            r#"{"moves":[4,0],"starter":"X""#,
+            r#"{"moves":[4,,0],"starter":"X"}"#,
+            r#"{"moves":[4,0,],"starter":"X"}"#,
+            r#"{"moves":[4,-1],"starter":"X"}"#,
+            r#"{"moves":[4 0],"starter":"X"}"#,
+            r#"{"moves":"4,0","starter":"X"}"#,
+            r#"{"moves":[4,0],"starter":"Z"}"#,
+            r#"{"moves":[4,0]}"#,
+            r#"{"moves":[4,0],"starter":"X","extra":1}"#,
+            r#"{"moves":[4,0],"moves":[1],"starter":"X"}"#,
+            r#"{"moves":[4,0],"starter":"X"} trailing"#,
+            r#"{"size":"4","moves":[4,0],"starter":"X"}"#,
+            r#"{"size":3,"size":3,"moves":[4,0],"starter":"X"}"#,
+        ];
+        for json in malformed {
+            assert!(
+                matches!(
+                    Game::from_move_json(json),
+                    Err(ParseError::MalformedJson(_))
+                ),
+                "{json} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_move_json_illegal_move() {
+        assert_eq!(
+            Game::from_move_json(r#"{"moves":[4,4],"starter":"X"}"#),
+            Err(ParseError::IllegalMove(1))
+        );
+        assert_eq!(
+            Game::from_move_json(r#"{"moves":[9],"starter":"X"}"#),
+            Err(ParseError::IllegalMove(0))
+        );
+    }
+
+    #[test]
+    fn test_puzzle_mate_in_one() {
+        let game = Game::from_puzzle("XX.OO.... X").unwrap();
+        assert_eq!(game.current_player(), Player::Human);
+        assert_eq!(game.winning_moves(), vec![2]);
+
+        // Same board, O to move
+        let game = Game::from_puzzle("XX.OO.... O").unwrap();
+        assert_eq!(game.winning_moves(), vec![5]);
+    }
+
+    #[test]
+    fn test_puzzle_parse_errors() {
+        assert_eq!(
+            Game::from_puzzle("XX.OO...."),
+            Err(ParseError::InvalidToken("XX.OO....".into()))
+        );
+        assert_eq!(
+            Game::from_puzzle("XX.OO.... Z"),
+            Err(ParseError::InvalidToken("Z".into()))
+        );
+        assert_eq!(
+            Game::from_puzzle("XX.OO... X"),
+            Err(ParseError::WrongLength {
+                expected: 9,
+                found: 8
+            })
+        );
+        assert_eq!(
+            Game::from_puzzle("XX.O..... X"),
+            Err(ParseError::InvalidPosition(
+                InvalidPosition::WrongPlayerToMove(Player::Human)
+            ))
+        );
+        assert_eq!(
+            Game::from_puzzle("XXX.O.... O"),
+            Err(ParseError::InvalidPosition(
+                InvalidPosition::MarkCountMismatch { human: 3, ai: 1 }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_malformed_notation() {
+        assert_eq!(
+            Game::from_notation("1. X:5 O:5"),
+            Err(ParseError::IllegalMove(1))
+        );
+        assert_eq!(
+            Game::from_notation("1. X:5 X:1"),
+            Err(ParseError::OutOfTurn(1))
+        );
+        assert_eq!(
+            Game::from_notation("1. X:5 O:1 3. X:3"),
+            Err(ParseError::MoveNumber {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Game::from_notation("1. X:10"),
+            Err(ParseError::InvalidToken("X:10".to_string()))
+        );
+        assert_eq!(
+            Game::from_notation("X:5"),
+            Err(ParseError::InvalidToken("X:5".to_string()))
+        );
+        assert_eq!(
+            Game::from_notation("1. X:5 2. O:1"),
+            Err(ParseError::InvalidToken("2.".to_string()))
+        );
+        assert_eq!(
+            Game::from_notation("1. X:5 O:1 2."),
+            Err(ParseError::InvalidToken("2.".to_string()))
+        );
+    }
+}
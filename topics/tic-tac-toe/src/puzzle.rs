@@ -0,0 +1,192 @@
+//! Puzzles with a known solution, for interactive training.
+//!
+//! A puzzle is a starting position and the line of moves that solves it,
+//! the opponent's replies included. The player's moves are checked against
+//! that line one step at a time.
+
+use crate::ai::{AI, Outcome};
+use crate::game::{Game, GameState};
+use crate::rng::Rng;
+use crate::types::Player;
+
+/// Longest forced win on a 3x3 board, in plies: the winner's three moves
+/// and the two replies between them
+pub const LONGEST_FORCED_WIN: usize = 5;
+
+/// A position to solve and its solution line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The position the puzzle starts from
+    pub start: Game,
+    /// The moves of both sides that solve the puzzle, in order
+    pub solution: Vec<usize>,
+}
+
+impl Puzzle {
+    /// Creates a puzzle from its starting position and solution line
+    pub fn new(start: Game, solution: Vec<usize>) -> Self {
+        Puzzle { start, solution }
+    }
+
+    /// Generates a 3x3 puzzle whose side to move has a forced win taking at
+    /// least `min_plies_to_win` plies, both sides' moves counted
+    ///
+    /// Positions are reached by random legal play from the empty board,
+    /// starting over whenever a game ends first; the same seed always
+    /// gives the same puzzle. The solution is the fastest forced win, as
+    /// found by `AI::forced_win_in`.
+    ///
+    /// # Panics
+    /// Panics if `min_plies_to_win` is above `LONGEST_FORCED_WIN`, as no
+    /// such puzzle exists
+    pub fn random(seed: u64, min_plies_to_win: usize) -> Puzzle {
+        assert!(
+            min_plies_to_win <= LONGEST_FORCED_WIN,
+            "no 3x3 position has a forced win of {min_plies_to_win} plies"
+        );
+        let mut rng = Rng::new(seed);
+
+        loop {
+            let mut game = Game::new();
+            while let Some(position) = rng.choose(&game.available_moves()) {
+                if !game.apply_move(position) || game.state() != GameState::InProgress {
+                    break;
+                }
+                if let Some(line) = AI::forced_win_in(&game, game.max_remaining_moves())
+                    && line.len() >= min_plies_to_win
+                {
+                    return Puzzle::new(game, line);
+                }
+            }
+        }
+    }
+
+    /// Returns true if `position` is the solution's move at `move_index`
+    /// (0-based, counting both sides' moves)
+    /// Steps past the end of the solution never match
+    pub fn check(&self, move_index: usize, position: usize) -> bool {
+        self.solution.get(move_index) == Some(&position)
+    }
+
+    /// Estimates how hard the puzzle is: the number of moves the side to
+    /// move needs to force a win, plus one per decoy
+    ///
+    /// A decoy is a move that gives the win away although it looks at least
+    /// as good by `Game::heuristic` as the best winning move. Returns 0 if
+    /// the side to move has no forced win at all.
+    pub fn difficulty(&self) -> u32 {
+        let game = &self.start;
+        let mover = game.current_player();
+        let Some(line) = AI::forced_win_in(game, game.max_remaining_moves()) else {
+            return 0;
+        };
+
+        // Scores from the mover's point of view, None for moves that lose
+        // the forced win
+        let winning = match mover {
+            Player::Human => Outcome::HumanWins,
+            Player::AI => Outcome::AiWins,
+        };
+        let sign = if mover == Player::AI { 1 } else { -1 };
+        let moves: Vec<(i32, bool)> = game
+            .available_moves()
+            .into_iter()
+            .filter_map(|position| {
+                let next = game.peek(position)?;
+                Some((sign * next.heuristic(), AI::classify(&next) == winning))
+            })
+            .collect();
+
+        let best_winning = moves
+            .iter()
+            .filter(|&&(_, wins)| wins)
+            .map(|&(heuristic, _)| heuristic)
+            .max()
+            .unwrap_or(i32::MAX);
+        let decoys = moves
+            .iter()
+            .filter(|&&(heuristic, wins)| !wins && heuristic >= best_winning)
+            .count();
+
+        line.len().div_ceil(2) as u32 + decoys as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// X blocks on 6, which forks 3 and 7; O can only stop one of them
+    fn mate_in_two() -> Puzzle {
+        let start = Game::from_puzzle("X.O.O...X X").unwrap();
+        Puzzle::new(start, vec![6, 3, 7])
+    }
+
+    #[test]
+    fn test_check_solution_moves() {
+        let puzzle = mate_in_two();
+        assert!(puzzle.check(0, 6));
+        assert!(puzzle.check(1, 3));
+        assert!(puzzle.check(2, 7));
+
+        // Replaying the solution from the start wins for X
+        let mut game = puzzle.start.clone();
+        for &position in &puzzle.solution {
+            assert!(game.make_move(position));
+        }
+        assert_eq!(game.state(), GameState::Won(Player::Human));
+    }
+
+    #[test]
+    fn test_difficulty() {
+        // Completing the top row is the obvious, only sensible move
+        let mate_in_one = Puzzle::new(Game::from_puzzle("XX.OO.... X").unwrap(), vec![2]);
+        assert_eq!(mate_in_one.difficulty(), 1);
+
+        // The forcing block on 6 takes two moves to cash in
+        let puzzle = mate_in_two();
+        assert!(puzzle.difficulty() > mate_in_one.difficulty());
+
+        // Without a forced win there is nothing to solve
+        let drawn = Puzzle::new(Game::new(), Vec::new());
+        assert_eq!(drawn.difficulty(), 0);
+    }
+
+    #[test]
+    fn test_random_puzzles_are_solvable() {
+        for (seed, min_plies) in [(1, 1), (2, 3), (3, 5), (4, 5)] {
+            let puzzle = Puzzle::random(seed, min_plies);
+            let start = &puzzle.start;
+            assert_eq!(start.state(), GameState::InProgress);
+
+            // The fastest forced win is the solution, and long enough
+            let line = AI::forced_win_in(start, start.max_remaining_moves()).unwrap();
+            assert!(line.len() >= min_plies);
+            assert_eq!(puzzle.solution, line);
+
+            let mut game = start.clone();
+            for &position in &puzzle.solution {
+                assert!(game.make_move(position));
+            }
+            assert_eq!(game.state(), GameState::Won(start.current_player()));
+            assert_eq!(Puzzle::random(seed, min_plies), puzzle);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "no 3x3 position")]
+    fn test_random_puzzle_too_long() {
+        Puzzle::random(0, LONGEST_FORCED_WIN + 1);
+    }
+
+    #[test]
+    fn test_check_wrong_moves() {
+        let puzzle = mate_in_two();
+        // Blocking without forking, or forking without blocking
+        assert!(!puzzle.check(0, 3));
+        assert!(!puzzle.check(0, 7));
+        assert!(!puzzle.check(2, 3));
+        // Past the end of the solution
+        assert!(!puzzle.check(3, 5));
+    }
+}
@@ -0,0 +1,50 @@
+//! Small seedable pseudo-random number generator.
+//!
+//! Randomized features (AI variety, random games) take an explicit seed so
+//! that tests stay deterministic. This is SplitMix64: fast, tiny and more
+//! than good enough for picking moves; it is not cryptographically secure.
+
+/// Seedable SplitMix64 generator
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed; the same seed yields the same sequence
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random index in `0..n`
+    ///
+    /// # Panics
+    /// Panics if `n` is zero
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        assert!(n > 0, "gen_range called with an empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Returns a random float in `[0, 1)`
+    pub fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random element of `items`, or None if it is empty
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> Option<T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(items[self.gen_range(items.len())])
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Async move sources for driving a game from networked or otherwise
+//! asynchronous inputs.
+//!
+//! The core `Game` stays synchronous; only the driver loop awaits moves.
+//! No particular runtime is required: blocking work (such as reading
+//! stdin) runs on a background thread and wakes the awaiting task.
+
+use crate::ai::AI;
+use crate::game::{Game, GameState};
+use crate::types::Player;
+use std::future::Future;
+use std::io::{self, BufRead};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// Something that can asynchronously produce the next move for a game
+pub trait AsyncMoveSource {
+    /// Returns the position (0-8) to play next, or None to stop the game
+    fn next_move(&mut self, game: &Game) -> impl Future<Output = Option<usize>>;
+}
+
+/// Move source backed by the Minimax AI
+pub struct AiSource {
+    ai: AI,
+}
+
+impl AiSource {
+    /// Wraps the given AI as a move source
+    pub fn new(ai: AI) -> Self {
+        AiSource { ai }
+    }
+}
+
+impl AsyncMoveSource for AiSource {
+    async fn next_move(&mut self, game: &Game) -> Option<usize> {
+        self.ai.find_best_move(game)
+    }
+}
+
+/// Move source reading positions 1-9 from standard input
+///
+/// Each read happens on a background thread so the awaiting task is not
+/// blocked. Lines that are not an available position are skipped; the end
+/// of input yields None.
+#[derive(Debug, Default)]
+pub struct StdinSource;
+
+impl StdinSource {
+    /// Creates a source reading from standard input
+    pub fn new() -> Self {
+        StdinSource
+    }
+}
+
+impl AsyncMoveSource for StdinSource {
+    async fn next_move(&mut self, game: &Game) -> Option<usize> {
+        let available = game.available_moves();
+        spawn_blocking(move || {
+            let mut stdin = io::stdin().lock();
+            loop {
+                let mut line = String::new();
+                if stdin.read_line(&mut line).ok()? == 0 {
+                    return None;
+                }
+                match line.trim().parse::<usize>() {
+                    Ok(num) if num >= 1 && available.contains(&(num - 1)) => {
+                        return Some(num - 1);
+                    }
+                    _ => continue,
+                }
+            }
+        })
+        .await
+    }
+}
+
+/// Drives `game` to completion, asking each side's source for its moves
+/// Returns the final state, or the current state if a source gave up
+/// (returned None or an illegal move)
+pub async fn play<H, A>(game: &mut Game, human: &mut H, ai: &mut A) -> GameState
+where
+    H: AsyncMoveSource,
+    A: AsyncMoveSource,
+{
+    while game.state() == GameState::InProgress {
+        let position = match game.current_player() {
+            Player::Human => human.next_move(game).await,
+            Player::AI => ai.next_move(game).await,
+        };
+
+        match position {
+            Some(position) if game.make_move(position) => {}
+            _ => break,
+        }
+    }
+
+    game.state()
+}
+
+/// Shared slot between a background thread and the future awaiting it
+struct TaskSlot<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// Future resolving to the result of a closure run on a background thread
+struct BlockingTask<T> {
+    slot: Arc<Mutex<TaskSlot<T>>>,
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Runs a blocking closure on its own thread and returns a future for its result
+fn spawn_blocking<T, F>(f: F) -> BlockingTask<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(TaskSlot {
+        result: None,
+        waker: None,
+    }));
+
+    let thread_slot = Arc::clone(&slot);
+    thread::spawn(move || {
+        let result = f();
+        let mut slot = thread_slot.lock().unwrap();
+        slot.result = Some(result);
+        if let Some(waker) = slot.waker.take() {
+            waker.wake();
+        }
+    });
+
+    BlockingTask { slot }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Minimal executor: polls the future until it completes
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::yield_now();
+        }
+    }
+
+    /// Source replaying a fixed list of moves
+    struct ScriptedSource {
+        moves: VecDeque<usize>,
+    }
+
+    impl AsyncMoveSource for ScriptedSource {
+        async fn next_move(&mut self, _game: &Game) -> Option<usize> {
+            self.moves.pop_front()
+        }
+    }
+
+    #[test]
+    fn test_scripted_sources_play_to_completion() {
+        let mut game = Game::new();
+        let mut human = ScriptedSource {
+            moves: VecDeque::from([0, 1, 2]),
+        };
+        let mut ai = ScriptedSource {
+            moves: VecDeque::from([3, 4]),
+        };
+
+        let state = block_on(play(&mut game, &mut human, &mut ai));
+
+        assert_eq!(state, GameState::Won(Player::Human));
+        assert_eq!(game.history(), &[0, 3, 1, 4, 2]);
+    }
+
+    #[test]
+    fn test_scripted_source_against_ai() {
+        let mut game = Game::new();
+        // After blocking the top row, O threatens the diagonal X leaves open
+        let mut human = ScriptedSource {
+            moves: VecDeque::from([0, 1, 8]),
+        };
+        let mut ai = AiSource::new(AI::new());
+
+        let state = block_on(play(&mut game, &mut human, &mut ai));
+
+        assert_eq!(state, GameState::Won(Player::AI));
+        assert_eq!(game.history(), &[0, 4, 1, 2, 8, 6]);
+    }
+
+    #[test]
+    fn test_spawn_blocking_result() {
+        assert_eq!(block_on(spawn_blocking(|| 6 * 7)), 42);
+    }
+}
@@ -0,0 +1,192 @@
+//! Pluggable player strategies.
+//!
+//! A `Strategy` picks a move for whoever is to play. The Minimax `AI`, a
+//! rule-based bot and a random bot are provided; users can implement the
+//! trait to plug in their own players and pit them against each other.
+
+use crate::ai::AI;
+use crate::game::{Game, GameState};
+use crate::rng::Rng;
+use crate::types::Player;
+use std::cell::RefCell;
+
+/// Anything that can choose a move in a game
+pub trait Strategy {
+    /// Returns the position (0-8) to play for the current player, or None
+    /// if there is no move to make
+    fn choose_move(&self, game: &Game) -> Option<usize>;
+}
+
+impl Strategy for AI {
+    fn choose_move(&self, game: &Game) -> Option<usize> {
+        if game.current_player() == Player::AI {
+            return self.find_best_move(game);
+        }
+
+        // Playing the human side: `find_best_move` only plays for the AI
+        self.all_best_moves(game).first().copied()
+    }
+}
+
+/// Fast bot following the classic tic-tac-toe rules of thumb:
+/// win, block, fork, center, corner, edge
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleBasedBot;
+
+impl Strategy for RuleBasedBot {
+    fn choose_move(&self, game: &Game) -> Option<usize> {
+        rule_based_move(game)
+    }
+}
+
+/// Bot playing uniformly random legal moves from a seeded generator
+#[derive(Debug, Clone)]
+pub struct RandomBot {
+    rng: RefCell<Rng>,
+}
+
+impl RandomBot {
+    /// Creates a random bot; the same seed plays the same moves
+    pub fn new(seed: u64) -> Self {
+        RandomBot {
+            rng: RefCell::new(Rng::new(seed)),
+        }
+    }
+}
+
+impl Strategy for RandomBot {
+    fn choose_move(&self, game: &Game) -> Option<usize> {
+        self.rng.borrow_mut().choose(&game.available_moves())
+    }
+}
+
+/// Picks a move for the current player using rules of thumb, without search
+pub fn rule_based_move(game: &Game) -> Option<usize> {
+    let available = game.available_moves();
+    if available.is_empty() || game.state() != GameState::InProgress {
+        return None;
+    }
+
+    let me = game.current_player();
+    let opponent = me.opponent();
+
+    // 1. Win, 2. block the opponent's win
+    if let Some(&position) = game.threats(me).first() {
+        return Some(position);
+    }
+    if let Some(&position) = game.threats(opponent).first() {
+        return Some(position);
+    }
+
+    // 3. Create a fork, 4. take the opponent's fork square
+    for player in [me, opponent] {
+        if let Some(&position) = available
+            .iter()
+            .find(|&&position| game.creates_fork(position, player))
+        {
+            return Some(position);
+        }
+    }
+
+    // 5. Center, 6. a corner, 7. an edge
+    if game.board().size() == 3 {
+        return [4, 0, 2, 6, 8, 1, 3, 5, 7]
+            .into_iter()
+            .find(|position| available.contains(position));
+    }
+
+    // Other board sizes: the first free cell
+    available.first().copied()
+}
+
+/// Plays `game` to the end, asking each side's strategy for its moves
+/// Stops early if a strategy returns no move or an illegal one
+pub fn play_game(game: &mut Game, human: &dyn Strategy, ai: &dyn Strategy) -> GameState {
+    while game.state() == GameState::InProgress {
+        let strategy = match game.current_player() {
+            Player::Human => human,
+            Player::AI => ai,
+        };
+
+        match strategy.choose_move(game) {
+            Some(position) if game.make_move(position) => {}
+            _ => break,
+        }
+    }
+
+    game.state()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Always plays the lowest free square
+    struct LowestIndex;
+
+    impl Strategy for LowestIndex {
+        fn choose_move(&self, game: &Game) -> Option<usize> {
+            game.available_moves().first().copied()
+        }
+    }
+
+    #[test]
+    fn test_custom_strategy_plays_to_completion() {
+        let mut game = Game::new();
+        let state = play_game(&mut game, &LowestIndex, &LowestIndex);
+
+        // X fills 0, 2, 4, 6 and wins on the anti-diagonal
+        assert_eq!(state, GameState::Won(Player::Human));
+        assert_eq!(game.history(), &[0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_minimax_never_loses_to_custom_strategy() {
+        let mut game = Game::new();
+        let state = play_game(&mut game, &LowestIndex, &AI::new());
+        assert_eq!(state, GameState::Won(Player::AI));
+    }
+
+    #[test]
+    fn test_minimax_plays_either_side() {
+        let mut game = Game::new();
+        let state = play_game(&mut game, &AI::new(), &LowestIndex);
+        assert_eq!(state, GameState::Won(Player::Human));
+    }
+
+    #[test]
+    fn test_rule_based_wins_then_blocks() {
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(3); // AI O
+        game.make_move(1); // Human X
+        game.make_move(4); // AI O
+        game.make_move(8); // Human X
+
+        // The AI can win at 5 and must otherwise block at 2: winning comes first
+        assert_eq!(RuleBasedBot.choose_move(&game), Some(5));
+
+        let mut game = Game::new();
+        game.make_move(0); // Human X
+        game.make_move(4); // AI O
+        game.make_move(1); // Human X
+        assert_eq!(RuleBasedBot.choose_move(&game), Some(2));
+    }
+
+    #[test]
+    fn test_random_bot_is_legal_and_seeded() {
+        for seed in 0..10 {
+            let mut game = Game::new();
+            let state = play_game(&mut game, &RandomBot::new(seed), &RandomBot::new(seed + 1));
+            assert_ne!(state, GameState::InProgress);
+
+            let mut replay = Game::new();
+            play_game(
+                &mut replay,
+                &RandomBot::new(seed),
+                &RandomBot::new(seed + 1),
+            );
+            assert_eq!(replay.history(), game.history());
+        }
+    }
+}
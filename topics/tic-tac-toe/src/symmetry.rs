@@ -0,0 +1,194 @@
+//! Board symmetries: the 8 rotations and reflections of the square.
+//!
+//! Two positions related by one of these transforms are strategically
+//! identical, which lets analysis collapse equivalent moves and positions.
+
+use crate::board::Board;
+use crate::types::Cell;
+
+/// A rotation or reflection of a square board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    /// Leaves the board unchanged
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    /// Half turn
+    Rotate180,
+    /// Quarter turn counter-clockwise
+    Rotate270,
+    /// Mirror left-right
+    FlipHorizontal,
+    /// Mirror top-bottom
+    FlipVertical,
+    /// Mirror along the top-left to bottom-right diagonal
+    FlipDiagonal,
+    /// Mirror along the top-right to bottom-left diagonal
+    FlipAntiDiagonal,
+}
+
+impl Transform {
+    /// Every element of the symmetry group, identity first
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+    ];
+
+    /// Returns where the cell at `position` of a `size` x `size` board ends
+    /// up after the transform
+    pub fn apply_index(self, position: usize, size: usize) -> usize {
+        let last = size - 1;
+        let (row, col) = (position / size, position % size);
+        let (row, col) = match self {
+            Transform::Identity => (row, col),
+            Transform::Rotate90 => (col, last - row),
+            Transform::Rotate180 => (last - row, last - col),
+            Transform::Rotate270 => (last - col, row),
+            Transform::FlipHorizontal => (row, last - col),
+            Transform::FlipVertical => (last - row, col),
+            Transform::FlipDiagonal => (col, row),
+            Transform::FlipAntiDiagonal => (last - col, last - row),
+        };
+        row * size + col
+    }
+
+    /// Returns a new board with every mark moved by the transform
+    pub fn apply(self, board: &Board) -> Board {
+        let size = board.size();
+        let mut transformed = Board::with_size(size).expect("existing boards have a valid size");
+        for (position, cell) in board.cells().iter().enumerate() {
+            if let Cell::Occupied(player) = cell {
+                transformed.make_move(self.apply_index(position, size), *player);
+            }
+        }
+        transformed
+    }
+}
+
+/// Returns the representative of the board's symmetry class
+/// This is the transform of the board with the smallest position id
+pub fn canonical_board(board: &Board) -> Board {
+    Transform::ALL
+        .iter()
+        .map(|transform| transform.apply(board))
+        .min_by_key(Board::position_id)
+        .unwrap_or_default()
+}
+
+/// Returns a key shared by all boards that are symmetric to each other
+/// This is the position id of the canonical board
+pub fn canonical_key(board: &Board) -> u64 {
+    canonical_board(board).position_id()
+}
+
+/// Returns a transform mapping board `a` onto board `b`, or None if the
+/// boards are not symmetric to each other
+/// When several transforms fit (symmetric boards), the first in
+/// `Transform::ALL` is returned, so equal boards give `Identity`
+pub fn relating_transform(a: &Board, b: &Board) -> Option<Transform> {
+    if a.size() != b.size() {
+        return None;
+    }
+    Transform::ALL
+        .into_iter()
+        .find(|transform| transform.apply(a) == *b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::BoardBuilder;
+    use crate::types::Player;
+
+    #[test]
+    fn test_rotation_moves_corners() {
+        assert_eq!(Transform::Rotate90.apply_index(0, 3), 2);
+        assert_eq!(Transform::Rotate90.apply_index(2, 3), 8);
+        assert_eq!(Transform::Rotate270.apply_index(0, 3), 6);
+        assert_eq!(Transform::FlipHorizontal.apply_index(3, 3), 5);
+        for transform in Transform::ALL {
+            assert_eq!(transform.apply_index(4, 3), 4);
+        }
+    }
+
+    #[test]
+    fn test_transforms_on_larger_board() {
+        assert_eq!(Transform::Rotate90.apply_index(0, 4), 3);
+        assert_eq!(Transform::Rotate180.apply_index(1, 4), 14);
+        assert_eq!(Transform::FlipDiagonal.apply_index(1, 4), 4);
+
+        let mut board = Board::with_size(4).unwrap();
+        board.make_move(1, Player::Human);
+        let rotated = Transform::Rotate90.apply(&board);
+        assert_eq!(rotated.size(), 4);
+        assert_eq!(rotated.cells_of(Player::Human), vec![7]);
+        assert_eq!(canonical_key(&board), canonical_key(&rotated));
+    }
+
+    #[test]
+    fn test_canonical_key_of_symmetric_boards() {
+        let board = BoardBuilder::new().x(0).o(1).build();
+        let rotated = Transform::Rotate90.apply(&board);
+        let mirrored = BoardBuilder::new().x(0).o(3).build();
+        let different = BoardBuilder::new().x(0).o(4).build();
+
+        assert_eq!(canonical_key(&board), canonical_key(&rotated));
+        assert_eq!(canonical_key(&board), canonical_key(&mirrored));
+        assert_ne!(canonical_key(&board), canonical_key(&different));
+    }
+
+    #[test]
+    fn test_canonical_board_is_a_transform() {
+        let board = BoardBuilder::new().x(8).o(5).build();
+        let canonical = canonical_board(&board);
+
+        assert!(Transform::ALL.iter().any(|t| t.apply(&board) == canonical));
+        assert_eq!(canonical_key(&board), canonical.position_id());
+        assert_eq!(canonical_board(&canonical), canonical);
+    }
+
+    #[test]
+    fn test_relating_transform() {
+        let board = BoardBuilder::new().x(0).o(1).build();
+        let rotated = Transform::Rotate90.apply(&board);
+        assert_eq!(
+            relating_transform(&board, &rotated),
+            Some(Transform::Rotate90)
+        );
+        assert_eq!(
+            relating_transform(&rotated, &board),
+            Some(Transform::Rotate270)
+        );
+        assert_eq!(
+            relating_transform(&board, &board),
+            Some(Transform::Identity)
+        );
+
+        // Every transform is found back
+        for transform in Transform::ALL {
+            let moved = transform.apply(&board);
+            let found = relating_transform(&board, &moved).unwrap();
+            assert_eq!(found.apply(&board), moved);
+        }
+    }
+
+    #[test]
+    fn test_relating_transform_unrelated() {
+        let corner = BoardBuilder::new().x(0).build();
+        let center = BoardBuilder::new().x(4).build();
+        assert_eq!(relating_transform(&corner, &center), None);
+
+        // Same marks, different owners
+        let o_corner = BoardBuilder::new().o(2).build();
+        assert_eq!(relating_transform(&corner, &o_corner), None);
+
+        let larger = Board::with_size(4).unwrap();
+        assert_eq!(relating_transform(&Board::new(), &larger), None);
+    }
+}
@@ -0,0 +1,674 @@
+//! Round-robin tournaments between strategies.
+
+use crate::game::{Game, GameState};
+use crate::strategy::{Strategy, play_game};
+use crate::types::Player;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Results of one participant in a tournament
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Name the strategy was entered under
+    pub name: String,
+    /// Games won
+    pub wins: u32,
+    /// Games drawn
+    pub draws: u32,
+    /// Games lost
+    pub losses: u32,
+}
+
+impl Record {
+    /// Creates an empty record for a participant
+    pub fn new(name: &str) -> Self {
+        Record {
+            name: name.to_string(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        }
+    }
+
+    /// Total number of games played
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Tournament points: 3 per win, 1 per draw
+    pub fn points(&self) -> u32 {
+        3 * self.wins + self.draws
+    }
+}
+
+/// Win/draw/loss table of a tournament, in entry order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standings {
+    records: Vec<Record>,
+    /// Pairs of participant indices (lower first) that played all their games
+    completed: Vec<(usize, usize)>,
+}
+
+impl Standings {
+    /// Creates a table with an empty record for each name
+    pub fn new(names: &[&str]) -> Self {
+        Standings {
+            records: names.iter().map(|name| Record::new(name)).collect(),
+            completed: Vec::new(),
+        }
+    }
+
+    /// Returns true if the participants at indices `a` and `b` have played
+    /// all their games against each other
+    pub fn is_completed(&self, a: usize, b: usize) -> bool {
+        self.completed.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Marks the pairing of the participants at `a` and `b` as complete
+    pub fn mark_completed(&mut self, a: usize, b: usize) {
+        if !self.is_completed(a, b) {
+            self.completed.push((a.min(b), a.max(b)));
+        }
+    }
+
+    /// Writes the standings and the completed pairings to `path`
+    ///
+    /// The file is plain text, one `record` or `completed` entry per line
+    /// with tab-separated fields, so names can't contain tabs or newlines.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = String::new();
+        for record in &self.records {
+            if record.name.contains(['\t', '\n', '\r']) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("name {:?} can't be saved", record.name),
+                ));
+            }
+            out.push_str(&format!(
+                "record\t{}\t{}\t{}\t{}\n",
+                record.name, record.wins, record.draws, record.losses
+            ));
+        }
+        for (a, b) in &self.completed {
+            out.push_str(&format!("completed\t{a}\t{b}\n"));
+        }
+        fs::write(path, out)
+    }
+
+    /// Reads standings written by `save`
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid standings line {line:?}"),
+            )
+        };
+
+        let mut standings = Standings::default();
+        for line in fs::read_to_string(path)?.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields[..] {
+                ["record", name, wins, draws, losses] => {
+                    let count = |field: &str| field.parse().map_err(|_| invalid(line));
+                    standings.records.push(Record {
+                        name: name.to_string(),
+                        wins: count(wins)?,
+                        draws: count(draws)?,
+                        losses: count(losses)?,
+                    });
+                }
+                ["completed", a, b] => {
+                    let index = |field: &str| match field.parse() {
+                        Ok(index) if index < standings.records.len() => Ok(index),
+                        _ => Err(invalid(line)),
+                    };
+                    let (a, b) = (index(a)?, index(b)?);
+                    standings.mark_completed(a, b);
+                }
+                _ => return Err(invalid(line)),
+            }
+        }
+        Ok(standings)
+    }
+
+    /// Returns every participant's record
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Returns the record of the named participant
+    pub fn get(&self, name: &str) -> Option<&Record> {
+        self.records.iter().find(|record| record.name == name)
+    }
+
+    /// Renders the standings as an aligned text table
+    ///
+    /// Names are left-aligned and numbers right-aligned, with column widths
+    /// fitted to the longest entry.
+    pub fn render(&self) -> String {
+        let header = ["Player", "W", "D", "L", "Pts"];
+        let rows: Vec<[String; 5]> = self
+            .records
+            .iter()
+            .map(|record| {
+                [
+                    record.name.clone(),
+                    record.wins.to_string(),
+                    record.draws.to_string(),
+                    record.losses.to_string(),
+                    record.points().to_string(),
+                ]
+            })
+            .collect();
+
+        let mut widths = header.map(|title| title.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let format_row = |cells: [&str; 5]| {
+            let mut line = format!("{:<width$}", cells[0], width = widths[0]);
+            for (cell, width) in cells.iter().zip(widths).skip(1) {
+                line.push_str(&format!("  {cell:>width$}"));
+            }
+            line.push('\n');
+            line
+        };
+
+        let mut out = format_row(header);
+        let rule = widths.map(|width| "-".repeat(width));
+        out.push_str(&format_row(rule.each_ref().map(String::as_str)));
+        for row in &rows {
+            out.push_str(&format_row(row.each_ref().map(String::as_str)));
+        }
+        out
+    }
+
+    /// Records the result of a game between the participants at indices
+    /// `first` and `second`; `first` played X (the Human side)
+    pub fn record_game(&mut self, first: usize, second: usize, state: GameState) {
+        match state {
+            GameState::Won(Player::Human) => {
+                self.records[first].wins += 1;
+                self.records[second].losses += 1;
+            }
+            GameState::Won(Player::AI) => {
+                self.records[first].losses += 1;
+                self.records[second].wins += 1;
+            }
+            GameState::Draw => {
+                self.records[first].draws += 1;
+                self.records[second].draws += 1;
+            }
+            GameState::InProgress => {}
+        }
+    }
+}
+
+/// Scoring rules of a `Match`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchConfig {
+    /// Points for winning a game
+    pub win_points: u32,
+    /// Points each player gets for a drawn game
+    pub draw_points: u32,
+    /// Points that end the match once a player reaches them
+    pub target: u32,
+    /// Most games the match may last, None to play until the target
+    pub max_games: Option<u32>,
+}
+
+impl MatchConfig {
+    /// A best-of-`games` series: one point per win, the majority of the
+    /// games wins it
+    pub fn best_of(games: u32) -> Self {
+        MatchConfig {
+            win_points: 1,
+            draw_points: 0,
+            target: games / 2 + 1,
+            max_games: Some(games),
+        }
+    }
+}
+
+impl Default for MatchConfig {
+    /// First to 3 wins, draws score nothing
+    fn default() -> Self {
+        MatchConfig {
+            win_points: 1,
+            draw_points: 0,
+            target: 3,
+            max_games: None,
+        }
+    }
+}
+
+/// A series of games between the Human and AI sides, played until one of
+/// them reaches the target score or, for a capped series, clinches it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    config: MatchConfig,
+    human_points: u32,
+    ai_points: u32,
+    games: u32,
+}
+
+impl Match {
+    /// Starts a match with the given scoring rules
+    pub fn new(config: MatchConfig) -> Self {
+        Match {
+            config,
+            human_points: 0,
+            ai_points: 0,
+            games: 0,
+        }
+    }
+
+    /// Returns the scoring rules
+    pub fn config(&self) -> MatchConfig {
+        self.config
+    }
+
+    /// Returns the points scored so far by `player`
+    pub fn points(&self, player: Player) -> u32 {
+        match player {
+            Player::Human => self.human_points,
+            Player::AI => self.ai_points,
+        }
+    }
+
+    /// Returns the number of finished games recorded
+    pub fn games(&self) -> u32 {
+        self.games
+    }
+
+    /// Scores a finished game
+    /// Games still in progress, and games after the match is decided, are ignored
+    pub fn record(&mut self, state: GameState) {
+        if self.is_decided() {
+            return;
+        }
+
+        match state {
+            GameState::Won(Player::Human) => self.human_points += self.config.win_points,
+            GameState::Won(Player::AI) => self.ai_points += self.config.win_points,
+            GameState::Draw => {
+                self.human_points += self.config.draw_points;
+                self.ai_points += self.config.draw_points;
+            }
+            GameState::InProgress => return,
+        }
+        self.games += 1;
+    }
+
+    /// Returns true once a player has reached the target score, or once
+    /// the games left in a capped match can't change its result
+    pub fn is_decided(&self) -> bool {
+        if self.human_points.max(self.ai_points) >= self.config.target {
+            return true;
+        }
+
+        match self.config.max_games {
+            None => false,
+            Some(max_games) => {
+                // Each game can bring the trailing player at most one win closer
+                let left = max_games.saturating_sub(self.games);
+                left == 0
+                    || self.human_points.abs_diff(self.ai_points) > left * self.config.win_points
+            }
+        }
+    }
+
+    /// Returns how many more games need playing: 0 once the match is decided,
+    /// so a clinched series skips its meaningless games
+    /// A match without `max_games` has no fixed length and reports 1 until
+    /// it is decided
+    pub fn remaining_games(&self) -> usize {
+        if self.is_decided() {
+            return 0;
+        }
+        match self.config.max_games {
+            Some(max_games) => (max_games - self.games) as usize,
+            None => 1,
+        }
+    }
+
+    /// Returns the winner of a decided match: the player with more points
+    /// None while the match goes on, or if a draw took both to the target
+    /// with equal scores
+    pub fn winner(&self) -> Option<Player> {
+        if !self.is_decided() {
+            return None;
+        }
+        match self.human_points.cmp(&self.ai_points) {
+            std::cmp::Ordering::Greater => Some(Player::Human),
+            std::cmp::Ordering::Less => Some(Player::AI),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// Plays every pair of strategies against each other and tallies the results
+///
+/// Each pair plays `games_per_pair` games with each side moving first, so
+/// neither gets the first-move advantage more often.
+pub fn round_robin(strategies: Vec<(&str, Box<dyn Strategy>)>, games_per_pair: usize) -> Standings {
+    let names: Vec<&str> = strategies.iter().map(|(name, _)| *name).collect();
+    let mut standings = Standings::new(&names);
+
+    for a in 0..strategies.len() {
+        for b in a + 1..strategies.len() {
+            play_pairing(&strategies, a, b, games_per_pair, &mut standings);
+        }
+    }
+
+    standings
+}
+
+/// Continues a round robin from `standings`, skipping the pairings already
+/// completed, and checkpoints the standings to `path` after each pairing
+///
+/// Start with `Standings::new` for a fresh run, or `Standings::load(path)`
+/// to resume one that was interrupted. The strategies must be entered in
+/// the same order, under the same names, as in `standings`.
+pub fn resume_round_robin(
+    strategies: Vec<(&str, Box<dyn Strategy>)>,
+    games_per_pair: usize,
+    mut standings: Standings,
+    path: impl AsRef<Path>,
+) -> io::Result<Standings> {
+    let names = strategies.iter().map(|(name, _)| *name);
+    if !names.eq(standings.records.iter().map(|record| record.name.as_str())) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "strategies don't match the participants of the standings",
+        ));
+    }
+
+    for a in 0..strategies.len() {
+        for b in a + 1..strategies.len() {
+            if standings.is_completed(a, b) {
+                continue;
+            }
+            play_pairing(&strategies, a, b, games_per_pair, &mut standings);
+            standings.mark_completed(a, b);
+            standings.save(path.as_ref())?;
+        }
+    }
+
+    Ok(standings)
+}
+
+/// Plays the games between the strategies at indices `a` and `b`, each
+/// side moving first `games_per_pair` times, and records the results
+fn play_pairing(
+    strategies: &[(&str, Box<dyn Strategy>)],
+    a: usize,
+    b: usize,
+    games_per_pair: usize,
+    standings: &mut Standings,
+) {
+    for _ in 0..games_per_pair {
+        for (first, second) in [(a, b), (b, a)] {
+            let mut game = Game::new();
+            let state = play_game(
+                &mut game,
+                strategies[first].1.as_ref(),
+                strategies[second].1.as_ref(),
+            );
+            standings.record_game(first, second, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::AI;
+    use crate::strategy::{RandomBot, RuleBasedBot};
+
+    #[test]
+    fn test_render_aligned_table() {
+        let mut standings = Standings::new(&["minimax", "rule-based-bot", "rnd"]);
+        // minimax beats both and draws the bot once; the bot beats random 10 times
+        standings.record_game(0, 1, GameState::Won(Player::Human));
+        standings.record_game(2, 0, GameState::Won(Player::AI));
+        standings.record_game(0, 1, GameState::Draw);
+        for _ in 0..10 {
+            standings.record_game(1, 2, GameState::Won(Player::Human));
+        }
+
+        assert_eq!(
+            standings.render(),
+            "Player           W  D   L  Pts\n\
+             --------------  --  -  --  ---\n\
+             minimax          2  1   0    7\n\
+             rule-based-bot  10  1   1   31\n\
+             rnd              0  0  11    0\n"
+        );
+    }
+
+    #[test]
+    fn test_match_custom_points() {
+        let mut series = Match::new(MatchConfig {
+            win_points: 3,
+            draw_points: 1,
+            target: 7,
+            max_games: None,
+        });
+
+        series.record(GameState::Won(Player::Human));
+        series.record(GameState::Draw);
+        series.record(GameState::InProgress);
+        assert_eq!(series.points(Player::Human), 4);
+        assert_eq!(series.points(Player::AI), 1);
+        assert_eq!(series.games(), 2);
+
+        series.record(GameState::Won(Player::AI));
+        series.record(GameState::Draw);
+        // 5 to 5: nobody has reached 7 yet
+        assert!(!series.is_decided());
+        assert_eq!(series.winner(), None);
+
+        series.record(GameState::Won(Player::AI));
+        assert!(series.is_decided());
+        assert_eq!(series.points(Player::AI), 8);
+        assert_eq!(series.winner(), Some(Player::AI));
+
+        // Decided matches ignore further games
+        series.record(GameState::Won(Player::Human));
+        assert_eq!(series.points(Player::Human), 5);
+        assert_eq!(series.games(), 5);
+    }
+
+    #[test]
+    fn test_match_default_first_to_three_wins() {
+        let mut series = Match::new(MatchConfig::default());
+        for _ in 0..5 {
+            series.record(GameState::Draw);
+        }
+        assert!(!series.is_decided());
+
+        for _ in 0..3 {
+            series.record(GameState::Won(Player::Human));
+        }
+        assert_eq!(series.winner(), Some(Player::Human));
+    }
+
+    #[test]
+    fn test_best_of_three_remaining_games() {
+        let mut series = Match::new(MatchConfig::best_of(3));
+        assert_eq!(series.remaining_games(), 3);
+
+        series.record(GameState::Won(Player::Human));
+        series.record(GameState::Won(Player::AI));
+        // 1-1: the third game decides
+        assert_eq!(series.remaining_games(), 1);
+
+        let mut series = Match::new(MatchConfig::best_of(3));
+        series.record(GameState::Won(Player::Human));
+        series.record(GameState::Won(Player::Human));
+        // 2-0 clinches it, game 3 needn't be played
+        assert_eq!(series.remaining_games(), 0);
+        assert_eq!(series.winner(), Some(Player::Human));
+    }
+
+    #[test]
+    fn test_best_of_clinched_by_draws() {
+        let mut series = Match::new(MatchConfig::best_of(5));
+        series.record(GameState::Won(Player::AI));
+        series.record(GameState::Won(Player::AI));
+        series.record(GameState::Draw);
+        assert_eq!(series.remaining_games(), 2);
+
+        // 2-0 with one game left: short of the target but out of reach
+        series.record(GameState::Draw);
+        assert!(series.is_decided());
+        assert_eq!(series.remaining_games(), 0);
+        assert_eq!(series.winner(), Some(Player::AI));
+
+        // A capped series can end level
+        let mut series = Match::new(MatchConfig::best_of(1));
+        series.record(GameState::Draw);
+        assert!(series.is_decided());
+        assert_eq!(series.winner(), None);
+
+        // Uncapped matches always have another game to play until decided
+        let mut series = Match::new(MatchConfig::default());
+        series.record(GameState::Draw);
+        assert_eq!(series.remaining_games(), 1);
+    }
+
+    #[test]
+    fn test_match_tied_at_target() {
+        let mut series = Match::new(MatchConfig {
+            win_points: 2,
+            draw_points: 1,
+            target: 3,
+            max_games: None,
+        });
+        series.record(GameState::Won(Player::Human));
+        series.record(GameState::Won(Player::AI));
+        series.record(GameState::Draw);
+        assert!(series.is_decided());
+        assert_eq!(series.winner(), None);
+    }
+
+    #[test]
+    fn test_round_robin_minimax_never_loses() {
+        let standings = round_robin(
+            vec![
+                ("minimax", Box::new(AI::new())),
+                ("random", Box::new(RandomBot::new(7))),
+            ],
+            2,
+        );
+
+        let minimax = standings.get("minimax").unwrap();
+        let random = standings.get("random").unwrap();
+        assert_eq!(minimax.games(), 4);
+        assert_eq!(minimax.losses, 0);
+        assert_eq!(minimax.wins, random.losses);
+        assert_eq!(minimax.draws, random.draws);
+    }
+
+    #[test]
+    fn test_round_robin_plays_every_pair() {
+        let standings = round_robin(
+            vec![
+                ("rules", Box::new(RuleBasedBot)),
+                ("random-a", Box::new(RandomBot::new(1))),
+                ("random-b", Box::new(RandomBot::new(2))),
+            ],
+            3,
+        );
+
+        // Each participant meets two others, 3 games each way
+        for record in standings.records() {
+            assert_eq!(record.games(), 12);
+        }
+        let wins: u32 = standings.records().iter().map(|r| r.wins).sum();
+        let losses: u32 = standings.records().iter().map(|r| r.losses).sum();
+        assert_eq!(wins, losses);
+    }
+
+    /// Returns a file path in the temp directory unique to this test run
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.txt", std::process::id()))
+    }
+
+    fn participants() -> Vec<(&'static str, Box<dyn Strategy>)> {
+        vec![
+            ("rules", Box::new(RuleBasedBot)),
+            ("random-a", Box::new(RandomBot::new(1))),
+            ("random-b", Box::new(RandomBot::new(2))),
+        ]
+    }
+
+    #[test]
+    fn test_save_and_load_standings() {
+        let path = temp_path("standings-round-trip");
+        let mut standings = Standings::new(&["rules", "random-a", "random-b"]);
+        standings.record_game(0, 1, GameState::Won(Player::Human));
+        standings.record_game(1, 0, GameState::Draw);
+        standings.mark_completed(1, 0);
+
+        standings.save(&path).unwrap();
+        let loaded = Standings::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, standings);
+        assert!(loaded.is_completed(0, 1));
+        assert!(!loaded.is_completed(0, 2));
+    }
+
+    #[test]
+    fn test_load_rejects_garbage() {
+        let path = temp_path("standings-garbage");
+        fs::write(&path, "record\trules\t1\ttwo\t3\n").unwrap();
+        let error = Standings::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_resume_skips_completed_pairings() {
+        let path = temp_path("standings-resume");
+
+        // A run interrupted after the first pairing, which went 2-0
+        let mut partial = Standings::new(&["rules", "random-a", "random-b"]);
+        partial.record_game(0, 1, GameState::Won(Player::Human));
+        partial.record_game(1, 0, GameState::Won(Player::AI));
+        partial.mark_completed(0, 1);
+        partial.save(&path).unwrap();
+
+        let resumed = Standings::load(&path).unwrap();
+        let standings = resume_round_robin(participants(), 3, resumed, &path).unwrap();
+
+        // rules vs random-a was not replayed; the other pairings played 6 games
+        let rules = standings.get("rules").unwrap();
+        let random_a = standings.get("random-a").unwrap();
+        assert_eq!(rules.games(), 2 + 6);
+        assert_eq!(random_a.games(), 2 + 6);
+        assert_eq!(standings.get("random-b").unwrap().games(), 12);
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            assert!(standings.is_completed(a, b));
+        }
+
+        // The last checkpoint holds the final standings
+        assert_eq!(Standings::load(&path).unwrap(), standings);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_resume_rejects_other_participants() {
+        let path = temp_path("standings-mismatch");
+        let standings = Standings::new(&["rules", "random-b", "random-a"]);
+        let error = resume_round_robin(participants(), 1, standings, &path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+}
@@ -23,6 +23,14 @@ impl Player {
             Player::AI => 'O',
         }
     }
+
+    /// Returns the display name of this player
+    pub fn name(&self) -> &'static str {
+        match self {
+            Player::Human => "Human",
+            Player::AI => "AI",
+        }
+    }
 }
 
 /// Represents a cell on the board